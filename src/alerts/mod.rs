@@ -0,0 +1,648 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Deserializer};
+
+use crate::data::hashrate::HashRateUnit;
+use crate::data::miner::MinerData;
+use crate::monitor::MonitorEvent;
+
+pub mod sinks;
+
+use sinks::{AlertSink, SinkConfig};
+
+/// A value derived from `MinerData` that alert rules can be written against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertMetric {
+    /// The hottest chip or board sensor reading, in celsius
+    ChipTemperature,
+    /// The hottest board (PCB) temperature, in celsius
+    BoardTemperature,
+    /// The average temperature of the miner, in celsius
+    AverageTemperature,
+    /// The current hashrate as a percentage of the expected hashrate
+    HashratePercent,
+    /// The number of consecutive failed polls, reported by the monitor after every failed poll
+    OfflinePolls,
+    /// The rejected shares as a percentage of all shares submitted to the pools
+    PoolRejectedPercent,
+    /// The current power consumption, in watts
+    Wattage,
+    /// The current efficiency, in J/TH
+    Efficiency,
+    /// The speed of the slowest fan, in RPM
+    FanRpm,
+}
+
+impl AlertMetric {
+    fn value(&self, data: &MinerData) -> Option<f64> {
+        match self {
+            AlertMetric::ChipTemperature => data
+                .hashboards
+                .iter()
+                .flat_map(|board| {
+                    board
+                        .chips
+                        .iter()
+                        .filter_map(|chip| chip.temperature)
+                        .chain(board.intake_temperature)
+                        .chain(board.outlet_temperature)
+                })
+                .map(|t| t.as_celsius())
+                .reduce(f64::max),
+            AlertMetric::BoardTemperature => data
+                .hashboards
+                .iter()
+                .filter_map(|board| board.board_temperature)
+                .map(|t| t.as_celsius())
+                .reduce(f64::max),
+            AlertMetric::AverageTemperature => data.average_temperature.map(|t| t.as_celsius()),
+            AlertMetric::HashratePercent => {
+                let hashrate = data
+                    .hashrate
+                    .as_ref()?
+                    .as_unit(HashRateUnit::TeraHash)
+                    .value;
+                // the boards only cover the whole miner when every one of them has an
+                // expected hashrate, otherwise the nominal hashrate of the model is used
+                let boards: Option<Vec<f64>> = data
+                    .hashboards
+                    .iter()
+                    .map(|board| {
+                        board
+                            .expected_hashrate
+                            .as_ref()
+                            .map(|hr| hr.as_unit(HashRateUnit::TeraHash).value)
+                    })
+                    .collect();
+                let expected = match boards {
+                    Some(boards) if !boards.is_empty() => boards.iter().sum(),
+                    _ => {
+                        data.device_info
+                            .hardware
+                            .nominal_hashrate
+                            .as_ref()?
+                            .as_unit(HashRateUnit::TeraHash)
+                            .value
+                    }
+                };
+                if expected > 0.0 {
+                    Some(hashrate / expected * 100.0)
+                } else {
+                    None
+                }
+            }
+            AlertMetric::OfflinePolls => Some(0.0),
+            AlertMetric::PoolRejectedPercent => {
+                let (accepted, rejected) = data.pools.iter().fold((0, 0), |(a, r), pool| {
+                    (
+                        a + pool.accepted_shares.unwrap_or(0),
+                        r + pool.rejected_shares.unwrap_or(0),
+                    )
+                });
+                if accepted + rejected > 0 {
+                    Some(rejected as f64 / (accepted + rejected) as f64 * 100.0)
+                } else {
+                    None
+                }
+            }
+            AlertMetric::Wattage => data.wattage.map(|w| w.as_watts()),
+            AlertMetric::Efficiency => data.efficiency,
+            AlertMetric::FanRpm => data
+                .fans
+                .iter()
+                .map(|fan| fan.rpm.as_rpm())
+                .reduce(f64::min),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum AlertCondition {
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AtOrAbove,
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    AtOrBelow,
+}
+
+impl AlertCondition {
+    fn matches(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            AlertCondition::Above => value > threshold,
+            AlertCondition::AtOrAbove => value >= threshold,
+            AlertCondition::Below => value < threshold,
+            AlertCondition::AtOrBelow => value <= threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A single alert rule, such as "chip temperature > 85 for 5m".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AlertRule {
+    /// The unique name of this rule
+    pub name: String,
+    /// The value this rule is checked against
+    pub metric: AlertMetric,
+    /// How the value is compared to the threshold
+    pub condition: AlertCondition,
+    /// The value at which the rule triggers
+    pub threshold: f64,
+    /// The value the metric has to cross back over before a firing alert resolves.
+    /// Defaults to `threshold`, set it below (or above) to add hysteresis.
+    pub clear_threshold: Option<f64>,
+    /// How long the condition has to hold before the alert fires
+    #[serde(rename = "for", default, deserialize_with = "deserialize_duration")]
+    pub duration: Duration,
+    #[serde(default = "default_severity")]
+    pub severity: AlertSeverity,
+    /// The names of the sinks notified by this rule, all sinks if empty
+    #[serde(default)]
+    pub sinks: Vec<String>,
+}
+
+fn default_severity() -> AlertSeverity {
+    AlertSeverity::Warning
+}
+
+/// A set of alert rules and notification sinks, usually loaded from a TOML file.
+///
+/// ```toml
+/// [[rule]]
+/// name = "chip-overheat"
+/// metric = "chip_temperature"
+/// condition = ">"
+/// threshold = 85.0
+/// clear_threshold = 80.0
+/// for = "5m"
+/// severity = "critical"
+///
+/// [[sink]]
+/// name = "console"
+/// type = "stdout"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AlertConfig {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<AlertRule>,
+    #[serde(default, rename = "sink")]
+    pub sinks: Vec<SinkConfig>,
+}
+
+impl AlertConfig {
+    /// Parse an alert configuration from a TOML string
+    pub fn from_toml(config: &str) -> Result<Self, AlertError> {
+        Ok(toml::from_str(config)?)
+    }
+
+    /// Load an alert configuration from a TOML file
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self, AlertError> {
+        let config = tokio::fs::read_to_string(path).await?;
+        Self::from_toml(&config)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// A notification produced when a rule starts or stops firing for a miner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    /// The name of the rule that changed state
+    pub rule: String,
+    pub severity: AlertSeverity,
+    pub state: AlertState,
+    /// The IP address of the miner
+    pub ip: IpAddr,
+    /// The hostname of the miner, if known
+    pub hostname: Option<String>,
+    pub metric: AlertMetric,
+    /// The metric value that caused the state change
+    pub value: f64,
+    pub threshold: f64,
+    /// The time of the state change, in seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+impl Display for Alert {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = match self.state {
+            AlertState::Firing => "FIRING",
+            AlertState::Resolved => "RESOLVED",
+        };
+        write!(
+            f,
+            "[{}] {:?} {} on {}{}: {:?} = {:.2} (threshold {})",
+            state,
+            self.severity,
+            self.rule,
+            self.ip,
+            self.hostname
+                .as_ref()
+                .map(|h| format!(" ({})", h))
+                .unwrap_or_default(),
+            self.metric,
+            self.value,
+            self.threshold,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RuleState {
+    Normal,
+    Pending { since: u64 },
+    Firing,
+}
+
+/// Evaluates alert rules against miner data and dispatches alerts to sinks.
+///
+/// State is tracked per rule and per miner, so a rule only fires once the condition has held
+/// for the rule's `for` duration, and only resolves once the value crosses the clear threshold.
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    sinks: Vec<(String, Box<dyn AlertSink>)>,
+    states: HashMap<(usize, IpAddr), RuleState>,
+}
+
+impl AlertEngine {
+    pub fn new(config: AlertConfig) -> Self {
+        let sinks = config
+            .sinks
+            .into_iter()
+            .map(|sink| (sink.name.clone(), sink.build()))
+            .collect();
+        Self {
+            rules: config.rules,
+            sinks,
+            states: HashMap::new(),
+        }
+    }
+
+    /// Add a custom sink, referenced by `name` in the rules
+    pub fn with_sink(mut self, name: impl Into<String>, sink: Box<dyn AlertSink>) -> Self {
+        self.sinks.push((name.into(), sink));
+        self
+    }
+
+    /// Evaluate a monitor event and return the alerts that changed state
+    pub fn evaluate(&mut self, event: &MonitorEvent) -> Vec<Alert> {
+        match event {
+            MonitorEvent::Data(data) => self.evaluate_data(data),
+            MonitorEvent::PollFailed { ip, failed_polls }
+            | MonitorEvent::Offline { ip, failed_polls } => {
+                self.evaluate_offline(*ip, *failed_polls as f64)
+            }
+            MonitorEvent::Online { ip } => self.evaluate_offline(*ip, 0.0),
+            MonitorEvent::Registry(_) => Vec::new(),
+        }
+    }
+
+    /// Evaluate every rule against a snapshot of miner data
+    pub fn evaluate_data(&mut self, data: &MinerData) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for idx in 0..self.rules.len() {
+            if let Some(value) = self.rules[idx].metric.value(data) {
+                alerts.extend(self.step(
+                    idx,
+                    data.ip,
+                    data.hostname.clone(),
+                    value,
+                    data.timestamp,
+                ));
+            }
+        }
+        alerts
+    }
+
+    fn evaluate_offline(&mut self, ip: IpAddr, failed_polls: f64) -> Vec<Alert> {
+        let timestamp = now();
+        let mut alerts = Vec::new();
+        for idx in 0..self.rules.len() {
+            if self.rules[idx].metric == AlertMetric::OfflinePolls {
+                alerts.extend(self.step(idx, ip, None, failed_polls, timestamp));
+            }
+        }
+        alerts
+    }
+
+    fn step(
+        &mut self,
+        idx: usize,
+        ip: IpAddr,
+        hostname: Option<String>,
+        value: f64,
+        timestamp: u64,
+    ) -> Option<Alert> {
+        let rule = &self.rules[idx];
+        let state = self.states.entry((idx, ip)).or_insert(RuleState::Normal);
+
+        let triggered = rule.condition.matches(value, rule.threshold);
+        let clear_threshold = rule.clear_threshold.unwrap_or(rule.threshold);
+
+        let (next, alert_state) = match *state {
+            RuleState::Normal | RuleState::Pending { .. } if !triggered => {
+                (RuleState::Normal, None)
+            }
+            RuleState::Normal if rule.duration.is_zero() => {
+                (RuleState::Firing, Some(AlertState::Firing))
+            }
+            RuleState::Normal => (RuleState::Pending { since: timestamp }, None),
+            RuleState::Pending { since }
+                if timestamp.saturating_sub(since) >= rule.duration.as_secs() =>
+            {
+                (RuleState::Firing, Some(AlertState::Firing))
+            }
+            RuleState::Pending { since } => (RuleState::Pending { since }, None),
+            RuleState::Firing if !rule.condition.matches(value, clear_threshold) => {
+                (RuleState::Normal, Some(AlertState::Resolved))
+            }
+            RuleState::Firing => (RuleState::Firing, None),
+        };
+        *state = next;
+
+        alert_state.map(|state| Alert {
+            rule: rule.name.clone(),
+            severity: rule.severity,
+            state,
+            ip,
+            hostname,
+            metric: rule.metric,
+            value,
+            threshold: rule.threshold,
+            timestamp,
+        })
+    }
+
+    /// Send an alert to every sink its rule is routed to
+    pub async fn notify(&self, alert: &Alert) -> Vec<AlertError> {
+        let routes = self
+            .rules
+            .iter()
+            .find(|rule| rule.name == alert.rule)
+            .map(|rule| rule.sinks.clone())
+            .unwrap_or_default();
+
+        let mut errors = Vec::new();
+        for (name, sink) in &self.sinks {
+            if routes.is_empty() || routes.contains(name) {
+                if let Err(e) = sink.notify(alert).await {
+                    errors.push(e);
+                }
+            }
+        }
+        errors
+    }
+
+    /// Evaluate and dispatch alerts for every event in a stream, such as a `MonitorStream`.
+    ///
+    /// Runs until the stream ends. Sink failures are reported to stderr and do not stop evaluation.
+    pub async fn run(mut self, events: impl Stream<Item = MonitorEvent>) {
+        futures::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            for alert in self.evaluate(&event) {
+                for error in self.notify(&alert).await {
+                    eprintln!("Failed to send alert {}: {}", alert.rule, error);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AlertError {
+    ConfigError(toml::de::Error),
+    IoError(std::io::Error),
+    HttpError(String),
+    SmtpError(String),
+}
+
+impl Display for AlertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertError::ConfigError(error) => write!(f, "Invalid alert configuration: {}", error),
+            AlertError::IoError(error) => write!(f, "IO error: {}", error),
+            AlertError::HttpError(message) => write!(f, "HTTP error: {}", message),
+            AlertError::SmtpError(message) => write!(f, "SMTP error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AlertError {}
+
+impl From<toml::de::Error> for AlertError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::ConfigError(value)
+    }
+}
+
+impl From<std::io::Error> for AlertError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+/// Parse durations such as `"90s"`, `"5m"`, `"1h"` or a plain number of seconds
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawDuration {
+        Seconds(u64),
+        Text(String),
+    }
+
+    match RawDuration::deserialize(deserializer)? {
+        RawDuration::Seconds(seconds) => Ok(Duration::from_secs(seconds)),
+        RawDuration::Text(text) => parse_duration(&text)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid duration: {}", text))),
+    }
+}
+
+fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (value, unit) = text.split_at(split);
+    let value: u64 = value.parse().ok()?;
+    let multiplier = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(value * multiplier))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Failed to get system time")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [[rule]]
+        name = "chip-overheat"
+        metric = "chip_temperature"
+        condition = ">"
+        threshold = 85.0
+        clear_threshold = 80.0
+        for = "5m"
+        severity = "critical"
+        sinks = ["console"]
+
+        [[rule]]
+        name = "offline"
+        metric = "offline_polls"
+        condition = ">="
+        threshold = 3
+
+        [[sink]]
+        name = "console"
+        type = "stdout"
+    "#;
+
+    #[test]
+    fn test_parse_config() {
+        let config = AlertConfig::from_toml(CONFIG).unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].duration, Duration::from_secs(300));
+        assert_eq!(config.rules[1].severity, AlertSeverity::Warning);
+        assert_eq!(config.sinks.len(), 1);
+    }
+
+    #[test]
+    fn test_debounce_and_hysteresis() {
+        let mut engine = AlertEngine::new(AlertConfig::from_toml(CONFIG).unwrap());
+        let ip = IpAddr::from([10, 0, 0, 1]);
+
+        // must hold for 5 minutes before firing
+        assert!(engine.step(0, ip, None, 90.0, 0).is_none());
+        assert!(engine.step(0, ip, None, 90.0, 120).is_none());
+        let alert = engine.step(0, ip, None, 90.0, 300).unwrap();
+        assert_eq!(alert.state, AlertState::Firing);
+
+        // stays firing until the value drops to the clear threshold
+        assert!(engine.step(0, ip, None, 84.0, 360).is_none());
+        let alert = engine.step(0, ip, None, 79.0, 420).unwrap();
+        assert_eq!(alert.state, AlertState::Resolved);
+
+        // a dip below the threshold resets the pending timer
+        assert!(engine.step(0, ip, None, 90.0, 500).is_none());
+        assert!(engine.step(0, ip, None, 70.0, 600).is_none());
+        assert!(engine.step(0, ip, None, 90.0, 800).is_none());
+    }
+
+    #[test]
+    fn test_offline_polls() {
+        let mut engine = AlertEngine::new(AlertConfig::from_toml(CONFIG).unwrap());
+        let ip = IpAddr::from([10, 0, 0, 1]);
+        let failed = |failed_polls| MonitorEvent::PollFailed { ip, failed_polls };
+
+        assert!(engine.evaluate(&failed(1)).is_empty());
+        assert!(engine.evaluate(&failed(2)).is_empty());
+        let alerts = engine.evaluate(&failed(3));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, "offline");
+        assert_eq!(alerts[0].state, AlertState::Firing);
+
+        // the monitor's own offline event does not fire the rule a second time
+        let offline = MonitorEvent::Offline {
+            ip,
+            failed_polls: 3,
+        };
+        assert!(engine.evaluate(&offline).is_empty());
+        assert!(engine.evaluate(&failed(4)).is_empty());
+
+        let alerts = engine.evaluate(&MonitorEvent::Online { ip });
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_hashrate_percent() {
+        use crate::data::board::BoardData;
+        use crate::data::hashrate::HashRate;
+
+        let terahash = |value| HashRate {
+            value,
+            unit: HashRateUnit::TeraHash,
+            algo: String::from("SHA256"),
+        };
+        let board = |position, expected: Option<f64>| BoardData {
+            position,
+            hashrate: None,
+            expected_hashrate: expected.map(terahash),
+            board_temperature: None,
+            intake_temperature: None,
+            outlet_temperature: None,
+            expected_chips: None,
+            working_chips: None,
+            serial_number: None,
+            chips: vec![],
+            voltage: None,
+            frequency: None,
+            tuned: None,
+            active: None,
+        };
+        let mut data = MinerData::empty(IpAddr::from([10, 0, 0, 1]));
+        data.hashrate = Some(terahash(45.0));
+
+        data.hashboards = vec![board(0, Some(30.0)), board(1, Some(30.0))];
+        assert_eq!(AlertMetric::HashratePercent.value(&data), Some(75.0));
+
+        // a board without an expected hashrate falls back to the nominal hashrate
+        data.hashboards = vec![board(0, Some(30.0)), board(1, None)];
+        let nominal = data
+            .device_info
+            .hardware
+            .nominal_hashrate
+            .as_ref()
+            .expect("the S19 has a nominal hashrate")
+            .as_unit(HashRateUnit::TeraHash)
+            .value;
+        assert_eq!(
+            AlertMetric::HashratePercent.value(&data),
+            Some(45.0 / nominal * 100.0)
+        );
+
+        data.device_info.hardware.nominal_hashrate = None;
+        assert_eq!(AlertMetric::HashratePercent.value(&data), None);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("15"), Some(Duration::from_secs(15)));
+        assert_eq!(parse_duration("5x"), None);
+    }
+}
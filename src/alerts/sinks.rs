@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use super::{Alert, AlertError, AlertState};
+
+/// A destination for alert notifications.
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    async fn notify(&self, alert: &Alert) -> Result<(), AlertError>;
+}
+
+/// Configuration for one of the built-in sinks.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SinkConfig {
+    /// The name rules use to route alerts to this sink
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: SinkKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkKind {
+    /// Print alerts to standard output
+    Stdout,
+    /// POST alerts as JSON to a URL
+    Webhook { url: String },
+    /// Send alerts as plain text email through an SMTP relay, without authentication
+    Smtp {
+        host: String,
+        #[serde(default = "default_smtp_port")]
+        port: u16,
+        from: String,
+        to: Vec<String>,
+    },
+}
+
+fn default_smtp_port() -> u16 {
+    25
+}
+
+impl SinkConfig {
+    pub(crate) fn build(self) -> Box<dyn AlertSink> {
+        match self.kind {
+            SinkKind::Stdout => Box::new(StdoutSink),
+            SinkKind::Webhook { url } => Box::new(WebhookSink::new(url)),
+            SinkKind::Smtp {
+                host,
+                port,
+                from,
+                to,
+            } => Box::new(SmtpSink::new(host, port, from, to)),
+        }
+    }
+}
+
+pub struct StdoutSink;
+
+#[async_trait]
+impl AlertSink for StdoutSink {
+    async fn notify(&self, alert: &Alert) -> Result<(), AlertError> {
+        println!("{}", alert);
+        Ok(())
+    }
+}
+
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, url }
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    async fn notify(&self, alert: &Alert) -> Result<(), AlertError> {
+        let body = json!({
+            "rule": alert.rule,
+            "severity": format!("{:?}", alert.severity).to_lowercase(),
+            "state": match alert.state {
+                AlertState::Firing => "firing",
+                AlertState::Resolved => "resolved",
+            },
+            "ip": alert.ip.to_string(),
+            "hostname": alert.hostname,
+            "metric": format!("{:?}", alert.metric),
+            "value": alert.value,
+            "threshold": alert.threshold,
+            "timestamp": alert.timestamp,
+            "message": alert.to_string(),
+        });
+
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AlertError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(AlertError::HttpError(format!(
+                "Webhook returned status {}",
+                response.status()
+            )))
+        }
+    }
+}
+
+/// Minimal SMTP client for relaying through a local mail server without authentication or TLS.
+pub struct SmtpSink {
+    host: String,
+    port: u16,
+    from: String,
+    to: Vec<String>,
+}
+
+impl SmtpSink {
+    pub fn new(host: String, port: u16, from: String, to: Vec<String>) -> Self {
+        Self {
+            host,
+            port,
+            from,
+            to,
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for SmtpSink {
+    async fn notify(&self, alert: &Alert) -> Result<(), AlertError> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port)).await?;
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+
+        expect_reply(&mut reader, 220).await?;
+        send_line(&mut writer, "HELO asic-rs").await?;
+        expect_reply(&mut reader, 250).await?;
+        send_line(&mut writer, &format!("MAIL FROM:<{}>", self.from)).await?;
+        expect_reply(&mut reader, 250).await?;
+        for to in &self.to {
+            send_line(&mut writer, &format!("RCPT TO:<{}>", to)).await?;
+            expect_reply(&mut reader, 250).await?;
+        }
+        send_line(&mut writer, "DATA").await?;
+        expect_reply(&mut reader, 354).await?;
+
+        let body = alert.to_string();
+        let message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\n\r\n{}\r\n.",
+            self.from,
+            self.to.join(", "),
+            email_subject(alert),
+            // lines starting with a dot have to be escaped, see RFC 5321 4.5.2
+            body.replace("\n.", "\n.."),
+        );
+        send_line(&mut writer, &message).await?;
+        expect_reply(&mut reader, 250).await?;
+        send_line(&mut writer, "QUIT").await?;
+
+        Ok(())
+    }
+}
+
+/// A short single line subject, as the alert body can span several lines
+fn email_subject(alert: &Alert) -> String {
+    format!("[{:?}] {} {}", alert.severity, alert.rule, alert.ip).replace(['\r', '\n'], " ")
+}
+
+async fn send_line(
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    line: &str,
+) -> Result<(), AlertError> {
+    writer.write_all(format!("{}\r\n", line).as_bytes()).await?;
+    Ok(())
+}
+
+/// Read a (possibly multi-line) SMTP reply and check its status code
+async fn expect_reply(
+    reader: &mut (impl AsyncBufReadExt + Unpin),
+    expected: u16,
+) -> Result<(), AlertError> {
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Err(AlertError::SmtpError(String::from(
+                "Connection closed by server",
+            )));
+        }
+        let code = line.get(..3).and_then(|c| c.parse::<u16>().ok());
+        // continuation lines look like `250-...`, the last line like `250 ...`
+        if line.as_bytes().get(3) == Some(&b'-') {
+            continue;
+        }
+        return match code {
+            Some(code) if code == expected => Ok(()),
+            _ => Err(AlertError::SmtpError(line.trim_end().to_string())),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::{AlertMetric, AlertSeverity, AlertState};
+    use std::net::IpAddr;
+
+    #[test]
+    fn test_email_subject() {
+        let alert = Alert {
+            rule: String::from("chip-overheat\nBcc: someone"),
+            severity: AlertSeverity::Critical,
+            state: AlertState::Firing,
+            ip: IpAddr::from([10, 0, 0, 1]),
+            hostname: None,
+            metric: AlertMetric::ChipTemperature,
+            value: 90.0,
+            threshold: 85.0,
+            timestamp: 0,
+        };
+        assert_eq!(
+            email_subject(&alert),
+            "[Critical] chip-overheat Bcc: someone 10.0.0.1"
+        );
+    }
+}
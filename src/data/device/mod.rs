@@ -1,5 +1,8 @@
+use measurements::{Power, Temperature};
 use serde::{Deserialize, Serialize};
 
+use crate::data::hashrate::HashRate;
+
 pub mod models;
 pub use models::MinerModel;
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -36,6 +39,18 @@ pub enum MinerMake {
     Braiins,
     #[serde(rename = "BitAxe")]
     BitAxe,
+    #[serde(rename = "Goldshell")]
+    Goldshell,
+    #[serde(rename = "IceRiver")]
+    IceRiver,
+    #[serde(rename = "Auradine")]
+    Auradine,
+    #[serde(rename = "Elphapex")]
+    Elphapex,
+    #[serde(rename = "Volcminer")]
+    Volcminer,
+    #[serde(rename = "Hammer")]
+    Hammer,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -50,9 +65,31 @@ pub enum HashAlgorithm {
     Blake2S256,
     #[serde(rename = "Kadena")]
     Kadena,
+    #[serde(rename = "Eaglesong")]
+    Eaglesong,
+    #[serde(rename = "Blake2BSHA3")]
+    Blake2BSHA3,
+    #[serde(rename = "KHeavyHash")]
+    KHeavyHash,
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HashAlgorithm::SHA256 => "SHA256",
+            HashAlgorithm::Scrypt => "Scrypt",
+            HashAlgorithm::X11 => "X11",
+            HashAlgorithm::Blake2S256 => "Blake2S256",
+            HashAlgorithm::Kadena => "Kadena",
+            HashAlgorithm::Eaglesong => "Eaglesong",
+            HashAlgorithm::Blake2BSHA3 => "Blake2BSHA3",
+            HashAlgorithm::KHeavyHash => "KHeavyHash",
+        };
+        write!(f, "{}", name)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DeviceInfo {
     pub make: MinerMake,
     pub model: MinerModel,
@@ -78,20 +115,39 @@ impl DeviceInfo {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum CoolingType {
+    #[serde(rename = "Air")]
+    Air,
+    #[serde(rename = "Hydro")]
+    Hydro,
+    #[serde(rename = "Immersion")]
+    Immersion,
+}
+
+/// Hardware specifications of a model, from the hardware database.
+///
+/// Fields are `None` when the value is not known for the model. The counts are known for
+/// most models, but the nominal hashrate and wattage, chip model and max temperature are
+/// only filled in for a few, so check them before relying on them.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct MinerHardware {
+    /// The number of chips on each hashboard
     pub chips: Option<u16>,
+    /// The number of fans, not counting PSU fans
     pub fans: Option<u8>,
+    /// The number of hashboards
     pub boards: Option<u8>,
-}
-
-impl From<&MinerModel> for MinerHardware {
-    fn from(model: &MinerModel) -> Self {
-        match model {
-            MinerModel::AntMiner(model_name) => Self::from(model_name),
-            MinerModel::WhatsMiner(model_name) => Self::from(model_name),
-            MinerModel::Braiins(model_name) => Self::from(model_name),
-            MinerModel::Bitaxe(model_name) => Self::from(model_name),
-        }
-    }
+    /// The rated hashrate of the whole miner
+    pub nominal_hashrate: Option<HashRate>,
+    /// The rated power draw of the whole miner
+    pub nominal_wattage: Option<Power>,
+    /// The hashing algorithm of the model
+    pub algo: Option<HashAlgorithm>,
+    /// How the model is cooled
+    pub cooling: Option<CoolingType>,
+    /// The ASIC model used on the hashboards
+    pub chip_model: Option<String>,
+    /// The highest safe chip temperature
+    pub max_temperature: Option<Temperature>,
 }
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, EnumIter)]
+pub enum AuradineModel {
+    #[serde(alias = "AT1500")]
+    AT1500,
+    #[serde(alias = "AT2860")]
+    AT2860,
+    #[serde(alias = "AT2880")]
+    AT2880,
+    #[serde(alias = "AH3880")]
+    AH3880,
+    #[serde(alias = "AI2500")]
+    AI2500,
+    #[serde(alias = "AI3680")]
+    AI3680,
+}
+
+impl AuradineModel {
+    /// Whether this is an immersion cooled (AI-series) model
+    pub fn is_immersion(&self) -> bool {
+        matches!(self, AuradineModel::AI2500 | AuradineModel::AI3680)
+    }
+}
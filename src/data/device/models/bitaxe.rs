@@ -1,5 +1,6 @@
 use serde::Serialize;
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+use strum::EnumIter;
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, EnumIter)]
 pub enum BitaxeModel {
     #[serde(alias = "BM1368")]
     Supra,
@@ -9,4 +10,14 @@ pub enum BitaxeModel {
     Max,
     #[serde(alias = "BM1366")]
     Ultra,
+    /// Bitaxe Ultra Hex, 6x BM1366
+    Hex,
+    /// Bitaxe Supra Hex, 6x BM1368
+    SupraHex,
+    /// NerdQAxe+, 4x BM1368
+    NerdQAxePlus,
+    /// NerdQAxe++, 4x BM1370
+    NerdQAxePlusPlus,
+    /// NerdOctaxe, 8x BM1370
+    NerdOctaxe,
 }
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, EnumIter)]
+pub enum ElphapexModel {
+    #[serde(alias = "DG1")]
+    DG1,
+    #[serde(alias = "DG1+", alias = "DG1PLUS")]
+    DG1Plus,
+    #[serde(alias = "DGHOME1")]
+    DGHome1,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+use crate::data::device::HashAlgorithm;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, EnumIter)]
+pub enum GoldshellModel {
+    #[serde(alias = "CK5")]
+    CK5,
+    #[serde(alias = "HS5")]
+    HS5,
+    #[serde(alias = "KD5")]
+    KD5,
+    #[serde(alias = "KDMAX")]
+    KDMax,
+    #[serde(alias = "KDBOX")]
+    KDBox,
+    #[serde(alias = "KDBOXII")]
+    KDBoxII,
+    #[serde(alias = "KDBOXPRO")]
+    KDBoxPro,
+    #[serde(alias = "KDLITE")]
+    KDLite,
+    #[serde(alias = "LT5")]
+    LT5,
+    #[serde(alias = "LT6")]
+    LT6,
+    #[serde(alias = "MINIDOGE")]
+    MiniDoge,
+    #[serde(alias = "MINIDOGEPRO")]
+    MiniDogePro,
+}
+
+impl GoldshellModel {
+    /// The hashing algorithm mined by this model
+    pub fn algo(&self) -> HashAlgorithm {
+        match self {
+            GoldshellModel::CK5 => HashAlgorithm::Eaglesong,
+            GoldshellModel::HS5 => HashAlgorithm::Blake2BSHA3,
+            GoldshellModel::KD5
+            | GoldshellModel::KDMax
+            | GoldshellModel::KDBox
+            | GoldshellModel::KDBoxII
+            | GoldshellModel::KDBoxPro
+            | GoldshellModel::KDLite => HashAlgorithm::Kadena,
+            GoldshellModel::LT5
+            | GoldshellModel::LT6
+            | GoldshellModel::MiniDoge
+            | GoldshellModel::MiniDogePro => HashAlgorithm::Scrypt,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, EnumIter)]
+pub enum HammerModel {
+    #[serde(alias = "D10")]
+    D10,
+}
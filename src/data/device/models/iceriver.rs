@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, EnumIter)]
+pub enum IceRiverModel {
+    #[serde(alias = "KS0")]
+    KS0,
+    #[serde(alias = "KS0PRO")]
+    KS0Pro,
+    #[serde(alias = "KS0ULTRA")]
+    KS0Ultra,
+    #[serde(alias = "KS1")]
+    KS1,
+    #[serde(alias = "KS2")]
+    KS2,
+    #[serde(alias = "KS3")]
+    KS3,
+    #[serde(alias = "KS3L")]
+    KS3L,
+    #[serde(alias = "KS3M")]
+    KS3M,
+    #[serde(alias = "KS5L")]
+    KS5L,
+    #[serde(alias = "KS5M")]
+    KS5M,
+    #[serde(alias = "KS7")]
+    KS7,
+}
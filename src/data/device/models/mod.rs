@@ -1,14 +1,26 @@
 use super::{MinerFirmware, MinerMake};
-use bitaxe::BitaxeModel;
 use antminer::AntMinerModel;
+use auradine::AuradineModel;
+use bitaxe::BitaxeModel;
 use braiins::BraiinsModel;
+use elphapex::ElphapexModel;
+use goldshell::GoldshellModel;
+use hammer::HammerModel;
+use iceriver::IceRiverModel;
 use serde::Serialize;
 use std::{fmt::Display, str::FromStr};
+use volcminer::VolcminerModel;
 use whatsminer::WhatsMinerModel;
 
 pub mod antminer;
+pub mod auradine;
 pub mod bitaxe;
 pub mod braiins;
+pub mod elphapex;
+pub mod goldshell;
+pub mod hammer;
+pub mod iceriver;
+pub mod volcminer;
 pub mod whatsminer;
 
 #[derive(Debug, Clone)]
@@ -48,12 +60,101 @@ impl FromStr for BraiinsModel {
     }
 }
 
+impl FromStr for GoldshellModel {
+    type Err = ModelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| ModelParseError)
+    }
+}
+
+impl FromStr for IceRiverModel {
+    type Err = ModelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| ModelParseError)
+    }
+}
+
+impl FromStr for AuradineModel {
+    type Err = ModelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| ModelParseError)
+    }
+}
+
+impl FromStr for ElphapexModel {
+    type Err = ModelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| ModelParseError)
+    }
+}
+
+impl FromStr for VolcminerModel {
+    type Err = ModelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| ModelParseError)
+    }
+}
+
+impl FromStr for HammerModel {
+    type Err = ModelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| ModelParseError)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum MinerModel {
     AntMiner(AntMinerModel),
     WhatsMiner(WhatsMinerModel),
     Braiins(BraiinsModel),
     Bitaxe(BitaxeModel),
+    Goldshell(GoldshellModel),
+    IceRiver(IceRiverModel),
+    Auradine(AuradineModel),
+    Elphapex(ElphapexModel),
+    Volcminer(VolcminerModel),
+    Hammer(HammerModel),
+    /// A model asic-rs does not know about yet, such as a new hardware revision.
+    ///
+    /// Backends still report live data for these, but the hardware fields are left empty.
+    Unknown { make: MinerMake, raw: String },
+}
+
+impl MinerModel {
+    /// Whether this is a model asic-rs does not know about
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, MinerModel::Unknown { .. })
+    }
+}
+
+impl Display for MinerModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MinerModel::AntMiner(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::WhatsMiner(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Braiins(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Bitaxe(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Goldshell(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::IceRiver(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Auradine(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Elphapex(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Volcminer(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Hammer(model_name) => write!(f, "{:?}", model_name),
+            MinerModel::Unknown { raw, .. } => write!(f, "{}", raw),
+        }
+    }
 }
 
 pub(crate) struct MinerModelFactory {
@@ -78,7 +179,38 @@ impl MinerModelFactory {
         self
     }
 
-    pub(crate) fn parse_model(&self, model_str: &str) -> Option<MinerModel> {
+    /// Parse a model string normalized from the `raw` string the miner reported, falling
+    /// back to [`MinerModel::Unknown`] with `raw` as it was when the make is known but the
+    /// model is not, so that new hardware revisions still get a backend
+    pub(crate) fn parse_model(&self, model_str: &str, raw: &str) -> Option<MinerModel> {
+        self.parse_known_model(model_str).or_else(|| {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                return None;
+            }
+            let make = match (self.make, self.firmware) {
+                (Some(make), _) => make,
+                (None, Some(MinerFirmware::BraiinsOS))
+                    if model_str.contains("BRAIINS") || model_str.contains("BMM") =>
+                {
+                    MinerMake::Braiins
+                }
+                // the remaining third party firmware only runs on AntMiners
+                (None, Some(MinerFirmware::BraiinsOS))
+                | (None, Some(MinerFirmware::LuxOS))
+                | (None, Some(MinerFirmware::Marathon))
+                | (None, Some(MinerFirmware::HiveOS))
+                | (None, Some(MinerFirmware::MSKMiner)) => MinerMake::AntMiner,
+                _ => return None,
+            };
+            Some(MinerModel::Unknown {
+                make,
+                raw: raw.to_owned(),
+            })
+        })
+    }
+
+    fn parse_known_model(&self, model_str: &str) -> Option<MinerModel> {
         match self.make {
             Some(MinerMake::AntMiner) => {
                 let model = AntMinerModel::from_str(model_str).ok();
@@ -94,6 +226,24 @@ impl MinerModelFactory {
                     None => None,
                 }
             }
+            Some(MinerMake::Goldshell) => GoldshellModel::from_str(model_str)
+                .ok()
+                .map(MinerModel::Goldshell),
+            Some(MinerMake::IceRiver) => IceRiverModel::from_str(model_str)
+                .ok()
+                .map(MinerModel::IceRiver),
+            Some(MinerMake::Auradine) => AuradineModel::from_str(model_str)
+                .ok()
+                .map(MinerModel::Auradine),
+            Some(MinerMake::Elphapex) => ElphapexModel::from_str(model_str)
+                .ok()
+                .map(MinerModel::Elphapex),
+            Some(MinerMake::Volcminer) => VolcminerModel::from_str(model_str)
+                .ok()
+                .map(MinerModel::Volcminer),
+            Some(MinerMake::Hammer) => HammerModel::from_str(model_str)
+                .ok()
+                .map(MinerModel::Hammer),
             None => match self.firmware {
                 Some(MinerFirmware::BraiinsOS) => {
                     if let Ok(model) = AntMinerModel::from_str(model_str) {
@@ -104,7 +254,10 @@ impl MinerModelFactory {
                     }
                     None
                 }
-                Some(MinerFirmware::LuxOS) => {
+                Some(MinerFirmware::LuxOS)
+                | Some(MinerFirmware::Marathon)
+                | Some(MinerFirmware::HiveOS)
+                | Some(MinerFirmware::MSKMiner) => {
                     if let Ok(model) = AntMinerModel::from_str(model_str) {
                         return Some(MinerModel::AntMiner(model));
                     }
@@ -117,3 +270,38 @@ impl MinerModelFactory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unknown_model() {
+        let model = MinerModelFactory::new()
+            .with_make(MinerMake::WhatsMiner)
+            .parse_model("M99S++_VZ10", "M99S++_VZ10");
+        assert_eq!(
+            model,
+            Some(MinerModel::Unknown {
+                make: MinerMake::WhatsMiner,
+                raw: String::from("M99S++_VZ10"),
+            })
+        );
+        assert_eq!(
+            crate::data::device::MinerHardware::from(&model.unwrap()),
+            crate::data::device::MinerHardware::default()
+        );
+
+        let model = MinerModelFactory::new()
+            .with_firmware(MinerFirmware::LuxOS)
+            .parse_model("ANTMINER S23", "Antminer S23");
+        assert!(matches!(
+            model,
+            Some(MinerModel::Unknown {
+                make: MinerMake::AntMiner,
+                ..
+            })
+        ));
+        assert_eq!(MinerModelFactory::new().parse_model("S19", "S19"), None);
+    }
+}
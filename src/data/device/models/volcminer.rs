@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, EnumIter)]
+pub enum VolcminerModel {
+    #[serde(alias = "D1")]
+    D1,
+    #[serde(alias = "D1LITE")]
+    D1Lite,
+    #[serde(alias = "D1HYDRO")]
+    D1Hydro,
+}
@@ -0,0 +1,306 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use super::device::MinerFirmware;
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// A firmware or API version, parsed into a form that can be compared.
+///
+/// Versions of the same kind are ordered, so firmware can be filtered with the usual operators:
+///
+/// ```
+/// use asic_rs::data::device::MinerFirmware;
+/// use asic_rs::data::firmware::FirmwareVersion;
+///
+/// let minimum = FirmwareVersion::dated(2024, 11, 1);
+/// let version = FirmwareVersion::parse(MinerFirmware::Stock, "20240625.16.REL");
+/// assert!(version < minimum);
+/// ```
+///
+/// Versions of different kinds, such as a date and a semantic version, are not comparable,
+/// so both `<` and `>=` are `false` between them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FirmwareVersion {
+    /// A build date, used by stock WhatsMiner and AntMiner firmware, such as `20241125.22.REL`
+    Dated {
+        year: u16,
+        month: u8,
+        day: u8,
+        /// The build number of that day, if the version has one
+        build: Option<u32>,
+        raw: String,
+    },
+    /// A semantic version, used by BraiinsOS, LuxOS and AxeOS, such as `v2.4.2`
+    Semantic {
+        major: u32,
+        minor: u32,
+        patch: u32,
+        /// Anything after the version numbers, such as a pre-release tag or commit hash
+        pre: Option<String>,
+        raw: String,
+    },
+    /// A version in a format that could not be parsed
+    Other(String),
+}
+
+impl FirmwareVersion {
+    /// Parse a firmware version, using the format preferred by the firmware family
+    pub fn parse(firmware: MinerFirmware, raw: &str) -> Self {
+        let raw = raw.trim();
+        let parsed = match firmware {
+            MinerFirmware::Stock => parse_dated(raw).or_else(|| parse_semantic(raw)),
+            _ => parse_semantic(raw).or_else(|| parse_dated(raw)),
+        };
+        parsed.unwrap_or_else(|| FirmwareVersion::Other(raw.to_owned()))
+    }
+
+    /// Parse an API version, which is always a semantic version such as `3.7`
+    pub fn parse_api(raw: &str) -> Self {
+        let raw = raw.trim();
+        parse_semantic(raw).unwrap_or_else(|| FirmwareVersion::Other(raw.to_owned()))
+    }
+
+    /// A date based version, for comparing against parsed versions
+    pub fn dated(year: u16, month: u8, day: u8) -> Self {
+        FirmwareVersion::Dated {
+            year,
+            month,
+            day,
+            build: None,
+            raw: format!("{:04}{:02}{:02}", year, month, day),
+        }
+    }
+
+    /// A semantic version, for comparing against parsed versions
+    pub fn semantic(major: u32, minor: u32, patch: u32) -> Self {
+        FirmwareVersion::Semantic {
+            major,
+            minor,
+            patch,
+            pre: None,
+            raw: format!("{}.{}.{}", major, minor, patch),
+        }
+    }
+
+    /// The version as reported by the miner
+    pub fn raw(&self) -> &str {
+        match self {
+            FirmwareVersion::Dated { raw, .. } => raw,
+            FirmwareVersion::Semantic { raw, .. } => raw,
+            FirmwareVersion::Other(raw) => raw,
+        }
+    }
+}
+
+impl PartialOrd for FirmwareVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // the raw string only breaks ties, so the ordering agrees with equality
+        match (self, other) {
+            (
+                FirmwareVersion::Dated {
+                    year,
+                    month,
+                    day,
+                    build,
+                    raw,
+                },
+                FirmwareVersion::Dated {
+                    year: o_year,
+                    month: o_month,
+                    day: o_day,
+                    build: o_build,
+                    raw: o_raw,
+                },
+            ) => {
+                Some((year, month, day, build, raw).cmp(&(o_year, o_month, o_day, o_build, o_raw)))
+            }
+            (
+                FirmwareVersion::Semantic {
+                    major,
+                    minor,
+                    patch,
+                    pre,
+                    raw,
+                },
+                FirmwareVersion::Semantic {
+                    major: o_major,
+                    minor: o_minor,
+                    patch: o_patch,
+                    pre: o_pre,
+                    raw: o_raw,
+                },
+            ) => Some(
+                // a pre-release sorts before the release it leads up to
+                (major, minor, patch, pre.is_none(), pre, raw).cmp(&(
+                    o_major,
+                    o_minor,
+                    o_patch,
+                    o_pre.is_none(),
+                    o_pre,
+                    o_raw,
+                )),
+            ),
+            (FirmwareVersion::Other(raw), FirmwareVersion::Other(o_raw)) if raw == o_raw => {
+                Some(Ordering::Equal)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for FirmwareVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw())
+    }
+}
+
+/// Parse a date based version, either `YYYYMMDD.build`, `YYYY-MM-DD-build` or an AntMiner
+/// compile time such as `Thu Apr 11 16:19:20 CST 2024`
+fn parse_dated(raw: &str) -> Option<FirmwareVersion> {
+    let digits: String = raw.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let (year, month, day, rest) = if digits.len() == 8 {
+        (
+            digits[..4].parse().ok()?,
+            digits[4..6].parse().ok()?,
+            digits[6..8].parse().ok()?,
+            &raw[8..],
+        )
+    } else if digits.len() == 4 && raw.as_bytes().get(4) == Some(&b'-') {
+        let mut parts = raw.get(..10)?.split('-');
+        (
+            parts.next()?.parse().ok()?,
+            parts.next()?.parse().ok()?,
+            parts.next()?.parse().ok()?,
+            raw.get(10..)?,
+        )
+    } else {
+        return parse_compile_time(raw);
+    };
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let build = rest
+        .trim_start_matches(['.', '-'])
+        .split(['.', '-'])
+        .next()
+        .and_then(|b| b.parse().ok());
+
+    Some(FirmwareVersion::Dated {
+        year,
+        month,
+        day,
+        build,
+        raw: raw.to_owned(),
+    })
+}
+
+fn parse_compile_time(raw: &str) -> Option<FirmwareVersion> {
+    let parts: Vec<&str> = raw.split_whitespace().collect();
+    let (month_idx, month) = parts.iter().enumerate().find_map(|(idx, part)| {
+        let month = MONTHS.iter().position(|m| m.eq_ignore_ascii_case(part))?;
+        Some((idx, month as u8 + 1))
+    })?;
+    let day = parts.get(month_idx + 1)?.parse().ok()?;
+    let year = parts.last()?.parse().ok()?;
+
+    Some(FirmwareVersion::Dated {
+        year,
+        month,
+        day,
+        build: None,
+        raw: raw.to_owned(),
+    })
+}
+
+/// Parse a semantic version with at least a major and minor number, such as `v2.4` or
+/// `2024.6.14.221829-2cb1b9d`
+fn parse_semantic(raw: &str) -> Option<FirmwareVersion> {
+    let mut rest = raw.trim_start_matches(['v', 'V']);
+    let mut numbers: Vec<u32> = Vec::with_capacity(3);
+    while numbers.len() < 3 {
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            break;
+        }
+        numbers.push(rest[..len].parse().ok()?);
+        rest = &rest[len..];
+
+        let next_is_number =
+            rest.starts_with('.') && rest[1..].starts_with(|c: char| c.is_ascii_digit());
+        if numbers.len() == 3 || !next_is_number {
+            break;
+        }
+        rest = &rest[1..];
+    }
+    if numbers.len() < 2 {
+        return None;
+    }
+
+    // everything after the first three numbers is kept as the pre-release tag
+    let pre = rest.trim_start_matches(['.', '-', '+', ' ']).to_owned();
+
+    Some(FirmwareVersion::Semantic {
+        major: numbers[0],
+        minor: numbers[1],
+        patch: numbers.get(2).copied().unwrap_or(0),
+        pre: (!pre.is_empty()).then_some(pre),
+        raw: raw.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_firmware_versions() {
+        let whatsminer = FirmwareVersion::parse(MinerFirmware::Stock, "20241125.22.REL");
+        assert!(matches!(
+            whatsminer,
+            FirmwareVersion::Dated {
+                year: 2024,
+                month: 11,
+                day: 25,
+                build: Some(22),
+                ..
+            }
+        ));
+        assert!(whatsminer >= FirmwareVersion::dated(2024, 11, 1));
+
+        let antminer = FirmwareVersion::parse(MinerFirmware::Stock, "Thu Apr 11 16:19:20 CST 2024");
+        assert!(matches!(
+            antminer,
+            FirmwareVersion::Dated {
+                year: 2024,
+                month: 4,
+                day: 11,
+                ..
+            }
+        ));
+
+        let axeos = FirmwareVersion::parse(MinerFirmware::Stock, "v2.4.2");
+        assert!(axeos > FirmwareVersion::semantic(2, 4, 1));
+        assert!(axeos < FirmwareVersion::semantic(2, 10, 0));
+
+        let luxos = FirmwareVersion::parse(MinerFirmware::LuxOS, "2024.6.14.221829-2cb1b9d");
+        assert!(matches!(
+            &luxos,
+            FirmwareVersion::Semantic { major: 2024, minor: 6, patch: 14, pre: Some(pre), .. }
+                if pre == "221829-2cb1b9d"
+        ));
+
+        // a multi-byte character where the date ends must not split it
+        let garbled = FirmwareVersion::parse(MinerFirmware::Stock, "2024-11-2é-1");
+        assert!(!matches!(garbled, FirmwareVersion::Dated { .. }));
+
+        let other = FirmwareVersion::parse(MinerFirmware::VNish, "unknown");
+        assert_eq!(other, FirmwareVersion::Other(String::from("unknown")));
+        assert!(!(other < axeos) && !(other >= axeos));
+    }
+}
@@ -14,6 +14,23 @@ pub enum HashRateUnit {
     YottaHash,
 }
 
+impl HashRateUnit {
+    /// The number of hashes per second represented by one of this unit
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            HashRateUnit::Hash => 1e0,
+            HashRateUnit::KiloHash => 1e3,
+            HashRateUnit::MegaHash => 1e6,
+            HashRateUnit::GigaHash => 1e9,
+            HashRateUnit::TeraHash => 1e12,
+            HashRateUnit::PetaHash => 1e15,
+            HashRateUnit::ExaHash => 1e18,
+            HashRateUnit::ZettaHash => 1e21,
+            HashRateUnit::YottaHash => 1e24,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct HashRate {
     /// The current amount of hashes being computed
@@ -24,6 +41,17 @@ pub struct HashRate {
     pub algo: String,
 }
 
+impl HashRate {
+    /// Convert this hashrate to a different unit
+    pub fn as_unit(&self, unit: HashRateUnit) -> HashRate {
+        HashRate {
+            value: self.value * self.unit.multiplier() / unit.multiplier(),
+            unit,
+            algo: self.algo.clone(),
+        }
+    }
+}
+
 impl Div<HashRate> for Power {
     type Output = f64;
 
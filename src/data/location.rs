@@ -0,0 +1,17 @@
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Location {
+    /// The site or facility the miner is installed at
+    pub site: Option<String>,
+    /// The container or building section within the site
+    pub container: Option<String>,
+    /// The rack within the container
+    pub rack: Option<String>,
+    /// The shelf within the rack
+    pub shelf: Option<String>,
+    /// The position of the miner on the shelf
+    pub position: Option<String>,
+    /// Any additional user defined labels
+    pub labels: BTreeMap<String, String>,
+}
@@ -4,8 +4,8 @@ use macaddr::MacAddr;
 use measurements::{Power, Temperature};
 
 use super::{
-    board::BoardData, device::DeviceInfo, fan::FanData, hashrate::HashRate, message::MinerMessage,
-    pool::PoolData,
+    board::BoardData, device::DeviceInfo, fan::FanData, firmware::FirmwareVersion,
+    hashrate::HashRate, location::Location, message::MinerMessage, pool::PoolData,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -16,6 +16,8 @@ pub struct MinerData {
     pub timestamp: u64,
     /// The IP address of the miner this data is for
     pub ip: IpAddr,
+    /// The hostname the miner was found by, when it was not given as an IP address
+    pub target_name: Option<String>,
     /// The MAC address of the miner this data is for
     pub mac: Option<MacAddr>,
     /// Hardware information about this miner
@@ -25,9 +27,9 @@ pub struct MinerData {
     /// The network hostname of the miner
     pub hostname: Option<String>,
     /// The API version of the miner
-    pub api_version: Option<String>,
+    pub api_version: Option<FirmwareVersion>,
     /// The firmware version of the miner
-    pub firmware_version: Option<String>,
+    pub firmware_version: Option<FirmwareVersion>,
     /// The type of control board on the miner
     pub control_board_version: Option<String>,
     /// The expected number of boards in the miner.
@@ -66,4 +68,53 @@ pub struct MinerData {
     pub is_mining: bool,
     /// The current pools configured on the miner
     pub pools: Vec<PoolData>,
+    /// The user assigned physical location and labels of the miner
+    pub location: Option<Location>,
+}
+
+#[cfg(test)]
+impl MinerData {
+    /// Data for an S19 with only the IP address filled in, for tests to build on
+    pub(crate) fn empty(ip: IpAddr) -> Self {
+        use super::device::models::antminer::AntMinerModel;
+        use super::device::{HashAlgorithm, MinerFirmware, MinerMake, MinerModel};
+
+        Self {
+            schema_version: String::new(),
+            timestamp: 0,
+            ip,
+            target_name: None,
+            mac: None,
+            device_info: DeviceInfo::new(
+                MinerMake::AntMiner,
+                MinerModel::AntMiner(AntMinerModel::S19),
+                MinerFirmware::Stock,
+                HashAlgorithm::SHA256,
+            ),
+            serial_number: None,
+            hostname: None,
+            api_version: None,
+            firmware_version: None,
+            control_board_version: None,
+            expected_hashboards: None,
+            hashboards: vec![],
+            hashrate: None,
+            expected_chips: None,
+            total_chips: None,
+            expected_fans: None,
+            fans: vec![],
+            psu_fans: vec![],
+            average_temperature: None,
+            fluid_temperature: None,
+            wattage: None,
+            wattage_limit: None,
+            efficiency: None,
+            light_flashing: None,
+            messages: vec![],
+            uptime: None,
+            is_mining: false,
+            pools: vec![],
+            location: None,
+        }
+    }
 }
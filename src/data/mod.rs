@@ -1,7 +1,9 @@
 pub mod board;
 pub mod device;
 pub mod fan;
+pub mod firmware;
 pub mod hashrate;
+pub mod location;
 pub mod message;
 pub mod miner;
 pub mod pool;
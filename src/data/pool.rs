@@ -31,6 +31,32 @@ pub struct PoolURL {
     pub pubkey: Option<String>,
 }
 
+impl PoolURL {
+    /// Parse a pool URL, returning `None` instead of panicking if it is invalid or uses an unknown scheme
+    pub fn parse(url: &str) -> Option<Self> {
+        let parsed = Url::parse(url).ok()?;
+        let scheme = match parsed.scheme() {
+            "stratum+tcp" => PoolScheme::StratumV1,
+            "stratum+ssl" => PoolScheme::StratumV1SSL,
+            "stratum2+tcp" => PoolScheme::StratumV2,
+            _ => return None,
+        };
+        let host = parsed.host_str()?.to_string();
+        let port = parsed.port().unwrap_or(80);
+        let path = parsed.path();
+        let pubkey = match path {
+            "" | "/" => None,
+            _ => Some(path[1..].to_string()),
+        };
+        Some(PoolURL {
+            scheme,
+            host,
+            port,
+            pubkey,
+        })
+    }
+}
+
 impl From<String> for PoolURL {
     fn from(url: String) -> Self {
         let parsed = Url::parse(&url).expect("Invalid pool URL");
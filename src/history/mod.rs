@@ -0,0 +1,544 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use macaddr::MacAddr;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+use crate::data::hashrate::HashRateUnit;
+use crate::data::miner::MinerData;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const RAW_EXTENSION: &str = "jsonl";
+const DOWNSAMPLED_EXTENSION: &str = "ds.jsonl";
+
+/// A flattened, serializable snapshot of the trend-relevant parts of `MinerData`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistorySample {
+    /// The time this data was gathered, in seconds since the unix epoch
+    pub timestamp: u64,
+    /// The IP address of the miner at the time of the sample
+    pub ip: IpAddr,
+    /// The MAC address of the miner
+    pub mac: Option<String>,
+    /// The model name of the miner
+    pub model: String,
+    /// The hashrate of the miner in TH/s
+    pub hashrate: Option<f64>,
+    /// The power consumption of the miner in watts
+    pub wattage: Option<f64>,
+    /// The efficiency of the miner in J/TH
+    pub efficiency: Option<f64>,
+    /// The average chip temperature of the miner in celsius
+    pub average_temperature: Option<f64>,
+    /// Per-hashboard values for this sample
+    pub hashboards: Vec<BoardSample>,
+    /// The number of raw samples averaged into this one, 1 for samples that were not downsampled
+    pub samples: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardSample {
+    /// The board position in the miner, indexed from 0
+    pub position: u8,
+    /// The hashrate of the board in TH/s
+    pub hashrate: Option<f64>,
+    /// The board temperature in celsius
+    pub temperature: Option<f64>,
+    /// The number of working chips on the board
+    pub working_chips: Option<u16>,
+}
+
+impl HistorySample {
+    /// The key used to link samples from the same miner, the MAC address if known, otherwise the IP
+    pub fn key(&self) -> String {
+        match &self.mac {
+            Some(mac) => mac.clone(),
+            None => self.ip.to_string(),
+        }
+    }
+}
+
+impl From<&MinerData> for HistorySample {
+    fn from(data: &MinerData) -> Self {
+        let hashrate = data
+            .hashrate
+            .as_ref()
+            .map(|hr| hr.as_unit(HashRateUnit::TeraHash).value);
+        let wattage = data.wattage.map(|w| w.as_watts());
+        let efficiency = data.efficiency.or(match (wattage, hashrate) {
+            (Some(w), Some(hr)) if hr > 0.0 => Some(w / hr),
+            _ => None,
+        });
+
+        Self {
+            timestamp: data.timestamp,
+            ip: data.ip,
+            mac: data.mac.map(|mac| mac.to_string()),
+            model: data.device_info.model.to_string(),
+            hashrate,
+            wattage,
+            efficiency,
+            average_temperature: data.average_temperature.map(|t| t.as_celsius()),
+            hashboards: data
+                .hashboards
+                .iter()
+                .map(|board| BoardSample {
+                    position: board.position,
+                    hashrate: board
+                        .hashrate
+                        .as_ref()
+                        .map(|hr| hr.as_unit(HashRateUnit::TeraHash).value),
+                    temperature: board
+                        .board_temperature
+                        .or(board.outlet_temperature)
+                        .map(|t| t.as_celsius()),
+                    working_chips: board.working_chips,
+                })
+                .collect(),
+            samples: 1,
+        }
+    }
+}
+
+/// Filter for reading samples back out of a [`HistoryStore`].
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    /// Only return samples for this MAC address
+    pub mac: Option<MacAddr>,
+    /// Only return samples for this IP address
+    pub ip: Option<IpAddr>,
+    /// Only return samples for this model name
+    pub model: Option<String>,
+    /// Only return samples taken at or after this timestamp
+    pub since: Option<u64>,
+    /// Only return samples taken before this timestamp
+    pub until: Option<u64>,
+}
+
+impl HistoryQuery {
+    fn matches(&self, sample: &HistorySample) -> bool {
+        self.mac
+            .is_none_or(|mac| sample.mac == Some(mac.to_string()))
+            && self.ip.is_none_or(|ip| sample.ip == ip)
+            && self
+                .model
+                .as_ref()
+                .is_none_or(|model| &sample.model == model)
+            && self.since.is_none_or(|since| sample.timestamp >= since)
+            && self.until.is_none_or(|until| sample.timestamp < until)
+    }
+}
+
+/// The average efficiency of one model over one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyEfficiency {
+    /// The day, formatted as `YYYY-MM-DD` in UTC
+    pub day: String,
+    /// The model name
+    pub model: String,
+    /// The average efficiency in J/TH
+    pub efficiency: f64,
+}
+
+/// Embedded time-series store for `MinerData` snapshots.
+///
+/// Samples are appended as JSON lines to one file per UTC day inside a directory.
+/// [`HistoryStore::maintain`] drops days older than the retention period and averages
+/// older raw samples into fixed-size buckets to keep the store small.
+pub struct HistoryStore {
+    dir: PathBuf,
+    retention: Duration,
+    downsample_after: Duration,
+    downsample_bucket: Duration,
+}
+
+impl HistoryStore {
+    /// Open a store in the given directory, creating it if needed
+    pub async fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).await?;
+
+        Ok(Self {
+            dir,
+            retention: Duration::from_secs(90 * SECONDS_PER_DAY),
+            downsample_after: Duration::from_secs(7 * SECONDS_PER_DAY),
+            downsample_bucket: Duration::from_secs(60 * 60),
+        })
+    }
+
+    /// Set how long samples are kept before being deleted
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Set the age after which samples are averaged into buckets of the given size
+    pub fn with_downsampling(mut self, after: Duration, bucket: Duration) -> Self {
+        self.downsample_after = after;
+        self.downsample_bucket = bucket.max(Duration::from_secs(1));
+        self
+    }
+
+    /// Append a snapshot of miner data to the store
+    pub async fn record(&self, data: &MinerData) -> io::Result<()> {
+        self.record_sample(&HistorySample::from(data)).await
+    }
+
+    /// Append an already flattened sample to the store
+    pub async fn record_sample(&self, sample: &HistorySample) -> io::Result<()> {
+        let day = sample.timestamp / SECONDS_PER_DAY;
+        let mut line = serde_json::to_string(sample)?;
+        line.push('\n');
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.day_path(day, RAW_EXTENSION))
+            .await?;
+        file.write_all(line.as_bytes()).await
+    }
+
+    /// Read all samples matching the query, ordered by timestamp
+    pub async fn query(&self, query: &HistoryQuery) -> io::Result<Vec<HistorySample>> {
+        let first_day = query.since.map(|t| t / SECONDS_PER_DAY);
+        let last_day = query.until.map(|t| t / SECONDS_PER_DAY);
+
+        let mut samples = Vec::new();
+        for (day, path) in self.day_files().await? {
+            if first_day.is_some_and(|first| day < first) || last_day.is_some_and(|last| day > last)
+            {
+                continue;
+            }
+            samples.extend(
+                read_samples(&path)
+                    .await?
+                    .into_iter()
+                    .filter(|sample| query.matches(sample)),
+            );
+        }
+        samples.sort_by_key(|sample| sample.timestamp);
+
+        Ok(samples)
+    }
+
+    /// Get `(timestamp, TH/s)` points for one hashboard of a miner over the given time window
+    pub async fn board_hashrate(
+        &self,
+        mac: MacAddr,
+        board: u8,
+        window: Duration,
+    ) -> io::Result<Vec<(u64, f64)>> {
+        let query = HistoryQuery {
+            mac: Some(mac),
+            since: Some(now().saturating_sub(window.as_secs())),
+            ..Default::default()
+        };
+
+        Ok(self
+            .query(&query)
+            .await?
+            .into_iter()
+            .filter_map(|sample| {
+                let hashrate = sample
+                    .hashboards
+                    .iter()
+                    .find(|b| b.position == board)?
+                    .hashrate?;
+                Some((sample.timestamp, hashrate))
+            })
+            .collect())
+    }
+
+    /// Get the average efficiency per model per day over the given time window
+    pub async fn daily_efficiency_by_model(
+        &self,
+        window: Duration,
+    ) -> io::Result<Vec<DailyEfficiency>> {
+        let query = HistoryQuery {
+            since: Some(now().saturating_sub(window.as_secs())),
+            ..Default::default()
+        };
+
+        let mut totals: BTreeMap<(u64, String), (f64, u32)> = BTreeMap::new();
+        for sample in self.query(&query).await? {
+            if let Some(efficiency) = sample.efficiency {
+                let entry = totals
+                    .entry((sample.timestamp / SECONDS_PER_DAY, sample.model))
+                    .or_insert((0.0, 0));
+                entry.0 += efficiency * sample.samples as f64;
+                entry.1 += sample.samples;
+            }
+        }
+
+        Ok(totals
+            .into_iter()
+            .map(|((day, model), (sum, count))| DailyEfficiency {
+                day: format_day(day),
+                model,
+                efficiency: sum / count as f64,
+            })
+            .collect())
+    }
+
+    /// Apply retention and downsampling, should be called periodically
+    pub async fn maintain(&self) -> io::Result<()> {
+        let today = now() / SECONDS_PER_DAY;
+        let retention_days = self.retention.as_secs() / SECONDS_PER_DAY;
+        let downsample_days = self.downsample_after.as_secs() / SECONDS_PER_DAY;
+
+        for (day, path) in self.day_files().await? {
+            let age = today.saturating_sub(day);
+            if age > retention_days {
+                fs::remove_file(&path).await?;
+            } else if age > downsample_days && !is_downsampled(&path) {
+                let downsampled_path = self.day_path(day, DOWNSAMPLED_EXTENSION);
+                let mut samples = read_samples(&path).await?;
+                // samples recorded late for an already downsampled day are merged into it
+                if fs::try_exists(&downsampled_path).await? {
+                    samples.extend(read_samples(&downsampled_path).await?);
+                }
+                let samples = downsample(samples, self.downsample_bucket);
+                let mut contents = String::new();
+                for sample in samples {
+                    contents.push_str(&serde_json::to_string(&sample)?);
+                    contents.push('\n');
+                }
+                fs::write(&downsampled_path, contents).await?;
+                fs::remove_file(&path).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn day_path(&self, day: u64, extension: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", format_day(day), extension))
+    }
+
+    async fn day_files(&self) -> io::Result<Vec<(u64, PathBuf)>> {
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let name = entry.file_name();
+            let Some(day) = name
+                .to_str()
+                .and_then(|name| name.split('.').next())
+                .and_then(parse_day)
+            else {
+                continue;
+            };
+            files.push((day, path));
+        }
+        files.sort();
+
+        Ok(files)
+    }
+}
+
+async fn read_samples(path: &Path) -> io::Result<Vec<HistorySample>> {
+    let contents = fs::read_to_string(path).await?;
+    // a partially written last line from a crash is skipped instead of failing the whole day
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn is_downsampled(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|p| p.ends_with(DOWNSAMPLED_EXTENSION))
+}
+
+/// Average samples of each miner into buckets of the given size
+fn downsample(samples: Vec<HistorySample>, bucket: Duration) -> Vec<HistorySample> {
+    let bucket = bucket.as_secs().max(1);
+    let mut buckets: BTreeMap<(String, u64), Vec<HistorySample>> = BTreeMap::new();
+    for sample in samples {
+        buckets
+            .entry((sample.key(), sample.timestamp / bucket * bucket))
+            .or_default()
+            .push(sample);
+    }
+
+    let mut result: Vec<HistorySample> = buckets
+        .into_iter()
+        .filter_map(|((_, start), group)| {
+            let last = group.last()?.clone();
+            let positions: Vec<u8> = last.hashboards.iter().map(|b| b.position).collect();
+
+            Some(HistorySample {
+                timestamp: start,
+                hashrate: average(&group, |s| s.hashrate),
+                wattage: average(&group, |s| s.wattage),
+                efficiency: average(&group, |s| s.efficiency),
+                average_temperature: average(&group, |s| s.average_temperature),
+                hashboards: positions
+                    .into_iter()
+                    .map(|position| {
+                        let board = |s: &HistorySample| {
+                            s.hashboards
+                                .iter()
+                                .find(|b| b.position == position)
+                                .cloned()
+                        };
+                        BoardSample {
+                            position,
+                            hashrate: average(&group, |s| board(s)?.hashrate),
+                            temperature: average(&group, |s| board(s)?.temperature),
+                            working_chips: average(&group, |s| {
+                                board(s)?.working_chips.map(f64::from)
+                            })
+                            .map(|chips| chips.round() as u16),
+                        }
+                    })
+                    .collect(),
+                samples: group.iter().map(|s| s.samples).sum(),
+                ..last
+            })
+        })
+        .collect();
+    result.sort_by_key(|sample| sample.timestamp);
+
+    result
+}
+
+/// Weighted average of a value across samples, ignoring samples where it is missing
+fn average(
+    samples: &[HistorySample],
+    value: impl Fn(&HistorySample) -> Option<f64>,
+) -> Option<f64> {
+    let (sum, count) = samples
+        .iter()
+        .fold((0.0, 0), |(sum, count), sample| match value(sample) {
+            Some(v) => (sum + v * sample.samples as f64, count + sample.samples),
+            None => (sum, count),
+        });
+
+    if count > 0 {
+        Some(sum / count as f64)
+    } else {
+        None
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Failed to get system time")
+        .as_secs()
+}
+
+/// Format a count of days since the unix epoch as `YYYY-MM-DD`
+fn format_day(day: u64) -> String {
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Parse a `YYYY-MM-DD` string into a count of days since the unix epoch
+fn parse_day(day: &str) -> Option<u64> {
+    let mut parts = day.splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let d: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+
+    // days-from-civil, the inverse of format_day
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    u64::try_from(era * 146_097 + doe - 719_468).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: u64, hashrate: f64) -> HistorySample {
+        HistorySample {
+            timestamp,
+            ip: IpAddr::from([10, 0, 0, 1]),
+            mac: Some(MacAddr::from([0, 1, 2, 3, 4, 5]).to_string()),
+            model: String::from("S19"),
+            hashrate: Some(hashrate),
+            wattage: Some(3000.0),
+            efficiency: Some(3000.0 / hashrate),
+            average_temperature: None,
+            hashboards: vec![BoardSample {
+                position: 2,
+                hashrate: Some(hashrate / 3.0),
+                temperature: Some(60.0),
+                working_chips: Some(76),
+            }],
+            samples: 1,
+        }
+    }
+
+    #[test]
+    fn test_day_round_trip() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(20_744), "2026-10-18");
+        assert_eq!(parse_day("2026-10-18"), Some(20_744));
+        assert_eq!(
+            parse_day("2024-02-29").map(format_day).as_deref(),
+            Some("2024-02-29")
+        );
+    }
+
+    #[test]
+    fn test_downsample_averages_buckets() {
+        let samples = vec![sample(3600, 90.0), sample(3700, 110.0), sample(7200, 100.0)];
+        let result = downsample(samples, Duration::from_secs(3600));
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].timestamp, 3600);
+        assert_eq!(result[0].samples, 2);
+        assert_eq!(result[0].hashrate, Some(100.0));
+        assert_eq!(result[1].hashrate, Some(100.0));
+    }
+
+    #[tokio::test]
+    async fn test_record_and_query_board_hashrate() {
+        let dir = std::env::temp_dir().join(format!("asic-rs-history-{}", std::process::id()));
+        let store = HistoryStore::open(&dir).await.unwrap();
+
+        let timestamp = now();
+        store
+            .record_sample(&sample(timestamp - 60, 90.0))
+            .await
+            .unwrap();
+        store.record_sample(&sample(timestamp, 99.0)).await.unwrap();
+
+        let points = store
+            .board_hashrate(
+                MacAddr::from([0, 1, 2, 3, 4, 5]),
+                2,
+                Duration::from_secs(SECONDS_PER_DAY),
+            )
+            .await
+            .unwrap();
+        assert_eq!(points, vec![(timestamp - 60, 30.0), (timestamp, 33.0)]);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -1,12 +1,28 @@
 use crate::miners::backends::traits::GetMinerData;
 use crate::miners::factory::MinerFactory;
+use crate::miners::target::{MinerTarget, TargetError};
 use std::error::Error;
-use std::net::IpAddr;
 
+pub mod alerts;
 pub mod data;
+pub mod history;
+pub mod location;
 pub mod miners;
+pub mod monitor;
+pub mod registry;
 
-pub async fn get_miner(ip: IpAddr) -> Result<Option<Box<dyn GetMinerData>>, Box<dyn Error>> {
+/// Get a miner by IP address, hostname, or either with a web port
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let by_name = asic_rs::get_miner("miner01.site").await?;
+/// let by_ip = asic_rs::get_miner(std::net::IpAddr::from([10, 0, 0, 5])).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_miner(
+    target: impl TryInto<MinerTarget, Error: Into<TargetError>>,
+) -> Result<Option<Box<dyn GetMinerData>>, Box<dyn Error>> {
     let factory = MinerFactory::new();
-    factory.get_miner(ip).await
+    factory.get_miner(target).await
 }
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use macaddr::MacAddr;
+use serde_json::Value;
+
+use crate::data::location::Location;
+use crate::data::miner::MinerData;
+use crate::miners::backends::auradine::AuradineControl;
+use crate::miners::backends::traits::{GetMinerData, RawCommandError};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{DataField, DataLocation};
+
+/// Mapping of miners to their physical [`Location`], keyed by IP or MAC address.
+///
+/// MAC entries take precedence, so a location follows a miner when DHCP moves it.
+#[derive(Debug, Clone, Default)]
+pub struct LocationMap {
+    by_ip: HashMap<IpAddr, Location>,
+    by_mac: HashMap<MacAddr, Location>,
+}
+
+impl LocationMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a CSV mapping with a header row.
+    ///
+    /// Each row needs an `ip` or `mac` column (or both). The `site`, `container`, `rack`,
+    /// `shelf` and `position` columns fill the location, any other column becomes a label.
+    ///
+    /// ```csv
+    /// ip,mac,site,container,rack,shelf,position,owner
+    /// 10.1.2.3,,north,c1,C12,3,4,hosting
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<Self, LocationError> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let header: Vec<String> = match lines.next() {
+            Some((_, line)) => parse_csv_line(line)
+                .into_iter()
+                .map(|h| h.to_lowercase())
+                .collect(),
+            None => return Ok(Self::new()),
+        };
+        if !header.iter().any(|h| h == "ip" || h == "mac") {
+            return Err(LocationError::MissingKeyColumn);
+        }
+
+        let mut map = Self::new();
+        for (idx, line) in lines {
+            let row = idx + 1;
+            let mut ip = None;
+            let mut mac = None;
+            let mut location = Location::default();
+
+            for (column, value) in header.iter().zip(parse_csv_line(line)) {
+                if value.is_empty() {
+                    continue;
+                }
+                match column.as_str() {
+                    "ip" => {
+                        ip = Some(
+                            IpAddr::from_str(&value)
+                                .map_err(|_| LocationError::InvalidValue { row, value })?,
+                        )
+                    }
+                    "mac" => {
+                        mac = Some(
+                            MacAddr::from_str(&value)
+                                .map_err(|_| LocationError::InvalidValue { row, value })?,
+                        )
+                    }
+                    "site" => location.site = Some(value),
+                    "container" => location.container = Some(value),
+                    "rack" => location.rack = Some(value),
+                    "shelf" => location.shelf = Some(value),
+                    "position" => location.position = Some(value),
+                    label => {
+                        location.labels.insert(label.to_string(), value);
+                    }
+                }
+            }
+
+            match (ip, mac) {
+                (None, None) => return Err(LocationError::MissingKey { row }),
+                (ip, mac) => {
+                    if let Some(ip) = ip {
+                        map.by_ip.insert(ip, location.clone());
+                    }
+                    if let Some(mac) = mac {
+                        map.by_mac.insert(mac, location);
+                    }
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Load a CSV mapping from a file, see [`LocationMap::from_csv`]
+    pub async fn load_csv(path: impl AsRef<Path>) -> Result<Self, LocationError> {
+        let csv = tokio::fs::read_to_string(path).await?;
+        Self::from_csv(&csv)
+    }
+
+    /// Assign a location to an IP address
+    pub fn insert_ip(&mut self, ip: IpAddr, location: Location) {
+        self.by_ip.insert(ip, location);
+    }
+
+    /// Assign a location to a MAC address
+    pub fn insert_mac(&mut self, mac: MacAddr, location: Location) {
+        self.by_mac.insert(mac, location);
+    }
+
+    /// Get the location assigned to an IP address
+    pub fn get_ip(&self, ip: IpAddr) -> Option<&Location> {
+        self.by_ip.get(&ip)
+    }
+
+    /// Get the location assigned to a MAC address
+    pub fn get_mac(&self, mac: MacAddr) -> Option<&Location> {
+        self.by_mac.get(&mac)
+    }
+
+    /// Find the location of the miner this data belongs to, by MAC first, then by IP
+    pub fn locate(&self, data: &MinerData) -> Option<&Location> {
+        data.mac
+            .and_then(|mac| self.by_mac.get(&mac))
+            .or_else(|| self.by_ip.get(&data.ip))
+    }
+
+    /// Fill in the location of a set of miner data, if one is assigned
+    pub fn apply(&self, data: &mut MinerData) {
+        if let Some(location) = self.locate(data) {
+            data.location = Some(location.clone());
+        }
+    }
+
+    /// Get the IP addresses of every miner in a group, for use as scan or monitor targets.
+    ///
+    /// Only entries with an IP address are returned, MAC-only entries can't be reached directly.
+    pub fn targets(&self, filter: &LocationFilter) -> Vec<IpAddr> {
+        let mut targets: Vec<IpAddr> = self
+            .by_ip
+            .iter()
+            .filter(|(_, location)| filter.matches(location))
+            .map(|(ip, _)| *ip)
+            .collect();
+        targets.sort();
+        targets
+    }
+}
+
+/// Selects miners by location and labels, such as "all of rack C12 at site north".
+///
+/// Every field that is set has to match, comparisons ignore case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocationFilter {
+    pub site: Option<String>,
+    pub container: Option<String>,
+    pub rack: Option<String>,
+    pub shelf: Option<String>,
+    pub labels: Vec<(String, String)>,
+}
+
+impl LocationFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_site(mut self, site: impl Into<String>) -> Self {
+        self.site = Some(site.into());
+        self
+    }
+
+    pub fn with_container(mut self, container: impl Into<String>) -> Self {
+        self.container = Some(container.into());
+        self
+    }
+
+    pub fn with_rack(mut self, rack: impl Into<String>) -> Self {
+        self.rack = Some(rack.into());
+        self
+    }
+
+    pub fn with_shelf(mut self, shelf: impl Into<String>) -> Self {
+        self.shelf = Some(shelf.into());
+        self
+    }
+
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.push((key.into(), value.into()));
+        self
+    }
+
+    /// Whether a location is part of this group
+    pub fn matches(&self, location: &Location) -> bool {
+        fn field_matches(filter: &Option<String>, value: &Option<String>) -> bool {
+            match (filter, value) {
+                (None, _) => true,
+                (Some(f), Some(v)) => f.eq_ignore_ascii_case(v),
+                (Some(_), None) => false,
+            }
+        }
+
+        field_matches(&self.site, &location.site)
+            && field_matches(&self.container, &location.container)
+            && field_matches(&self.rack, &location.rack)
+            && field_matches(&self.shelf, &location.shelf)
+            && self.labels.iter().all(|(key, value)| {
+                location
+                    .labels
+                    .get(key)
+                    .is_some_and(|v| v.eq_ignore_ascii_case(value))
+            })
+    }
+
+    /// Whether a set of miner data belongs to this group, data without a location never matches
+    pub fn matches_data(&self, data: &MinerData) -> bool {
+        data.location
+            .as_ref()
+            .is_some_and(|location| self.matches(location))
+    }
+
+    /// Keep only the miner data in this group
+    pub fn filter_data<'a>(
+        &'a self,
+        data: impl IntoIterator<Item = MinerData> + 'a,
+    ) -> impl Iterator<Item = MinerData> + 'a {
+        data.into_iter().filter(|d| self.matches_data(d))
+    }
+
+    /// Keep only the miner handles in this group
+    pub fn filter_miners(&self, miners: Vec<LocatedMiner>) -> Vec<LocatedMiner> {
+        miners
+            .into_iter()
+            .filter(|miner| miner.location().is_some_and(|l| self.matches(l)))
+            .collect()
+    }
+}
+
+/// A miner handle that carries its location, and adds it to every `MinerData` it collects.
+pub struct LocatedMiner {
+    miner: Box<dyn GetMinerData>,
+    ip: IpAddr,
+    /// The MAC address, once it is known from collected data
+    mac: Mutex<Option<MacAddr>>,
+    locations: Arc<LocationMap>,
+}
+
+impl LocatedMiner {
+    pub fn new(ip: IpAddr, miner: Box<dyn GetMinerData>, locations: Arc<LocationMap>) -> Self {
+        Self {
+            miner,
+            ip,
+            mac: Mutex::new(None),
+            locations,
+        }
+    }
+
+    /// Set the MAC address, if it is known before any data is collected
+    pub fn with_mac(self, mac: MacAddr) -> Self {
+        *self.mac.lock().unwrap() = Some(mac);
+        self
+    }
+
+    /// The IP address of this miner
+    pub fn ip(&self) -> IpAddr {
+        self.ip
+    }
+
+    /// The MAC address of this miner, if it is known
+    pub fn mac(&self) -> Option<MacAddr> {
+        *self.mac.lock().unwrap()
+    }
+
+    /// The location assigned to this miner, by MAC first once it is known, then by IP
+    pub fn location(&self) -> Option<&Location> {
+        self.mac()
+            .and_then(|mac| self.locations.get_mac(mac))
+            .or_else(|| self.locations.get_ip(self.ip))
+    }
+
+    /// Unwrap the underlying miner handle
+    pub fn into_inner(self) -> Box<dyn GetMinerData> {
+        self.miner
+    }
+}
+
+#[async_trait]
+impl GetMinerData for LocatedMiner {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut data = self.miner.get_data_fields(fields).await;
+        if data.mac.is_some() {
+            *self.mac.lock().unwrap() = data.mac;
+        }
+        self.locations.apply(&mut data);
+        data
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        self.miner.get_locations(data_field)
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        self.miner.send_raw(command, params).await
+    }
+
+    fn as_auradine(&self) -> Option<&dyn AuradineControl> {
+        self.miner.as_auradine()
+    }
+}
+
+#[derive(Debug)]
+pub enum LocationError {
+    /// The CSV header has neither an `ip` nor a `mac` column
+    MissingKeyColumn,
+    /// A row has neither an IP nor a MAC address
+    MissingKey {
+        row: usize,
+    },
+    /// A row has an IP or MAC address that could not be parsed
+    InvalidValue {
+        row: usize,
+        value: String,
+    },
+    IoError(std::io::Error),
+}
+
+impl Display for LocationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocationError::MissingKeyColumn => {
+                write!(f, "CSV header needs an ip or mac column")
+            }
+            LocationError::MissingKey { row } => {
+                write!(f, "Row {} has neither an ip nor a mac address", row)
+            }
+            LocationError::InvalidValue { row, value } => {
+                write!(f, "Row {} has an invalid address: {}", row, value)
+            }
+            LocationError::IoError(error) => write!(f, "IO error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for LocationError {}
+
+impl From<std::io::Error> for LocationError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+/// Split a CSV line into trimmed fields, supporting double quoted fields with `""` escapes
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "ip,mac,site,container,rack,shelf,position,owner\n\
+        10.1.2.3,,north,c1,C12,3,4,hosting\n\
+        10.1.2.4,00:11:22:33:44:55,north,c1,C12,3,5,\"Acme, Inc\"\n\
+        10.1.3.1,,north,c2,D01,1,1,\n";
+
+    #[test]
+    fn test_parse_csv_locations() {
+        let map = LocationMap::from_csv(CSV).unwrap();
+        let location = map.get_ip(IpAddr::from([10, 1, 2, 4])).unwrap();
+
+        assert_eq!(location.rack.as_deref(), Some("C12"));
+        assert_eq!(location.position.as_deref(), Some("5"));
+        assert_eq!(
+            location.labels.get("owner").map(String::as_str),
+            Some("Acme, Inc")
+        );
+        assert!(
+            map.by_mac
+                .contains_key(&MacAddr::from_str("00:11:22:33:44:55").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_filter_targets() {
+        let map = LocationMap::from_csv(CSV).unwrap();
+        let filter = LocationFilter::new().with_site("North").with_rack("c12");
+
+        assert_eq!(
+            map.targets(&filter),
+            vec![IpAddr::from([10, 1, 2, 3]), IpAddr::from([10, 1, 2, 4])]
+        );
+        assert_eq!(
+            map.targets(&LocationFilter::new().with_label("owner", "hosting")),
+            vec![IpAddr::from([10, 1, 2, 3])]
+        );
+    }
+
+    /// Reports a fixed MAC address, as if the miner had been moved to this IP
+    struct MovedMiner {
+        ip: IpAddr,
+        mac: MacAddr,
+    }
+
+    #[async_trait]
+    impl GetMinerData for MovedMiner {
+        async fn get_data_fields(&self, _fields: &[DataField]) -> MinerData {
+            let mut data = MinerData::empty(self.ip);
+            data.mac = Some(self.mac);
+            data
+        }
+
+        fn get_locations(&self, _data_field: DataField) -> &'static [DataLocation] {
+            &[]
+        }
+
+        async fn send_raw(
+            &self,
+            command: MinerCommand,
+            _params: Option<Value>,
+        ) -> Result<Value, RawCommandError> {
+            Err(RawCommandError::UnsupportedTransport(command))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_located_miner_prefers_mac() {
+        let map = Arc::new(LocationMap::from_csv(CSV).unwrap());
+        // the miner registered at 10.1.2.4 by MAC now answers on the address of another row
+        let ip = IpAddr::from([10, 1, 2, 3]);
+        let mac = MacAddr::from_str("00:11:22:33:44:55").unwrap();
+        let miner = LocatedMiner::new(ip, Box::new(MovedMiner { ip, mac }), map.clone());
+
+        assert_eq!(
+            miner.location().and_then(|l| l.position.as_deref()),
+            Some("4")
+        );
+
+        let data = miner.get_data().await;
+
+        assert_eq!(miner.mac(), Some(mac));
+        assert_eq!(
+            miner.location().and_then(|l| l.position.as_deref()),
+            Some("5")
+        );
+        assert_eq!(data.location.as_ref(), miner.location());
+
+        let known = LocatedMiner::new(ip, Box::new(MovedMiner { ip, mac }), map).with_mac(mac);
+        assert_eq!(
+            known.location().and_then(|l| l.position.as_deref()),
+            Some("5")
+        );
+    }
+
+    #[test]
+    fn test_missing_key_column() {
+        assert!(matches!(
+            LocationMap::from_csv("site,rack\nnorth,C12\n"),
+            Err(LocationError::MissingKeyColumn)
+        ));
+    }
+}
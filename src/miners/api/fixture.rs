@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::ApiClient;
+
+/// Answers commands with fixed responses, to test a backend's locations without a miner
+pub(crate) struct FixtureClient {
+    responses: HashMap<&'static str, Value>,
+}
+
+impl FixtureClient {
+    pub(crate) fn new(responses: impl IntoIterator<Item = (&'static str, Value)>) -> Self {
+        Self {
+            responses: responses.into_iter().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiClient for FixtureClient {
+    async fn send_command(&self, command: &'static str) -> Result<Value, String> {
+        self.responses
+            .get(command)
+            .cloned()
+            .ok_or_else(|| format!("No fixture for {}", command))
+    }
+}
@@ -1,6 +1,8 @@
 use async_trait::async_trait;
 use serde_json::Value;
 
+#[cfg(test)]
+pub(crate) mod fixture;
 pub mod rpc;
 pub mod web;
 
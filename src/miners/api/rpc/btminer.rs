@@ -5,18 +5,18 @@ use async_trait::async_trait;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::json;
-use std::net::IpAddr;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 pub struct BTMinerV3RPC {
-    ip: IpAddr,
+    host: String,
     port: u16,
 }
 
 impl BTMinerV3RPC {
-    pub fn new(ip: IpAddr, port: Option<u16>) -> Self {
+    /// Create a client for an IP address or hostname
+    pub fn new(host: impl ToString, port: Option<u16>) -> Self {
         Self {
-            ip,
+            host: host.to_string(),
             port: port.unwrap_or(4433),
         }
     }
@@ -62,7 +62,7 @@ impl SendRPCCommand for BTMinerV3RPC {
         T: DeserializeOwned,
         P: Serialize + Send,
     {
-        let mut stream = tokio::net::TcpStream::connect((self.ip, self.port))
+        let mut stream = tokio::net::TcpStream::connect((self.host.as_str(), self.port))
             .await
             .map_err(|_| RPCError::ConnectionFailed)?;
 
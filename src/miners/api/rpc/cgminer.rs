@@ -0,0 +1,96 @@
+use crate::miners::api::ApiClient;
+use crate::miners::api::rpc::errors::RPCError;
+use crate::miners::api::rpc::status::RPCCommandStatus;
+use crate::miners::api::rpc::traits::SendRPCCommand;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Client for the standard CGMiner RPC API, used by stock firmware on many clone makes
+pub struct CGMinerRPC {
+    host: String,
+    port: u16,
+}
+
+impl CGMinerRPC {
+    /// Create a client for an IP address or hostname
+    pub fn new(host: impl ToString, port: Option<u16>) -> Self {
+        Self {
+            host: host.to_string(),
+            port: port.unwrap_or(4028),
+        }
+    }
+}
+
+impl RPCCommandStatus {
+    fn from_cgminer(response: &str) -> Result<Self, RPCError> {
+        let value: Value = serde_json::from_str(response)?;
+        let message = value["STATUS"][0]["Msg"].as_str();
+
+        match value["STATUS"][0]["STATUS"].as_str() {
+            None => Err(RPCError::StatusCheckFailed(
+                message
+                    .unwrap_or("Unknown error when looking for status code")
+                    .to_owned(),
+            )),
+            Some(value) => Ok(Self::from_str(value, message)),
+        }
+    }
+}
+
+#[async_trait]
+impl SendRPCCommand for CGMinerRPC {
+    async fn send_command<T, P>(
+        &self,
+        command: &'static str,
+        param: Option<P>,
+    ) -> Result<T, RPCError>
+    where
+        T: DeserializeOwned,
+        P: Serialize + Send,
+    {
+        let mut stream = tokio::net::TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(|_| RPCError::ConnectionFailed)?;
+
+        // some clones reject a null parameter, so only send it when there is one
+        let request = match param {
+            Some(param) => json!({ "command": command, "parameter": param }),
+            None => json!({ "command": command }),
+        };
+
+        stream.write_all(request.to_string().as_bytes()).await?;
+
+        let mut buffer = Vec::new();
+        stream.read_to_end(&mut buffer).await?;
+
+        let response = String::from_utf8_lossy(&buffer)
+            .into_owned()
+            .replace('\0', "");
+
+        self.parse_rpc_result::<T>(&response)
+    }
+
+    fn parse_rpc_result<T>(&self, response: &str) -> Result<T, RPCError>
+    where
+        T: DeserializeOwned,
+    {
+        let status = RPCCommandStatus::from_cgminer(response)?;
+
+        match status.into_result() {
+            Ok(_) => Ok(serde_json::from_str(response)?),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiClient for CGMinerRPC {
+    async fn send_command(&self, command: &'static str) -> Result<Value, String> {
+        SendRPCCommand::send_command::<Value, ()>(self, command, None)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
@@ -1,4 +1,5 @@
 pub mod btminer;
+pub mod cgminer;
 pub mod errors;
 pub mod luxminer;
 pub mod status;
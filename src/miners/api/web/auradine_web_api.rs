@@ -0,0 +1,289 @@
+use crate::miners::api::ApiClient;
+use crate::miners::api::web::{WebAddress, WebClient, client_builder};
+use async_trait::async_trait;
+use reqwest::{Client, Method, Response, StatusCode};
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::timeout;
+
+/// Auradine REST API client, authenticating with a token from `/token`
+pub struct AuradineWebApi {
+    client: Client,
+    pub address: WebAddress,
+    username: String,
+    password: String,
+    token: RwLock<Option<String>>,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApiClient for AuradineWebApi {
+    async fn send_command(&self, command: &'static str) -> Result<Value, String> {
+        self.send_command(command, None, Method::GET)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl WebClient for AuradineWebApi {
+    fn address(&self) -> &WebAddress {
+        &self.address
+    }
+
+    fn address_mut(&mut self) -> &mut WebAddress {
+        &mut self.address
+    }
+}
+
+impl AuradineWebApi {
+    /// Create a new Auradine API client with the default credentials
+    pub fn new(ip: String, port: u16) -> Self {
+        let client = client_builder()
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            address: WebAddress::new(ip, port),
+            username: String::from("admin"),
+            password: String::from("admin"),
+            token: RwLock::new(None),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Set the timeout for API requests
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the credentials used to request a token
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.username = username.to_owned();
+        self.password = password.to_owned();
+        self
+    }
+
+    /// Request and store a new token
+    async fn login(&self) -> Result<String, AuradineError> {
+        let url = format!("{}/token", self.base_url());
+        let body = json!({
+            "command": "token",
+            "user": self.username,
+            "password": self.password,
+        });
+
+        let response = timeout(
+            self.timeout,
+            self.client
+                .post(url)
+                .json(&body)
+                .timeout(self.timeout)
+                .send(),
+        )
+        .await
+        .map_err(|_| AuradineError::Timeout)?
+        .map_err(|e| AuradineError::NetworkError(e.to_string()))?;
+        let json_data = parse_response(response).await?;
+
+        let token = json_data["Token"][0]["Token"]
+            .as_str()
+            .ok_or(AuradineError::AuthenticationFailed)?
+            .to_owned();
+        *self.token.write().await = Some(token.clone());
+
+        Ok(token)
+    }
+
+    /// Send a command to the miner, requesting a token first if needed
+    pub async fn send_command(
+        &self,
+        command: &str,
+        parameters: Option<Value>,
+        method: Method,
+    ) -> Result<Value, AuradineError> {
+        let url = format!("{}/{}", self.base_url(), command);
+
+        let token = match self.token.read().await.clone() {
+            Some(token) => token,
+            None => self.login().await?,
+        };
+
+        let response = self
+            .execute_request(&url, &method, parameters.clone(), &token)
+            .await?;
+        let json_data = match parse_response(response).await {
+            // tokens expire, request a new one once and retry
+            Err(AuradineError::AuthenticationFailed) => {
+                let token = self.login().await?;
+                let response = self
+                    .execute_request(&url, &method, parameters, &token)
+                    .await?;
+                parse_response(response).await?
+            }
+            result => result?,
+        };
+
+        Ok(json_data)
+    }
+
+    async fn execute_request(
+        &self,
+        url: &str,
+        method: &Method,
+        parameters: Option<Value>,
+        token: &str,
+    ) -> Result<Response, AuradineError> {
+        let mut builder = match *method {
+            Method::GET => self.client.get(url),
+            Method::POST => self.client.post(url),
+            _ => return Err(AuradineError::UnsupportedMethod(method.to_string())),
+        };
+        if let Some(params) = parameters {
+            builder = builder.json(&params);
+        }
+
+        timeout(
+            self.timeout,
+            builder.header("Token", token).timeout(self.timeout).send(),
+        )
+        .await
+        .map_err(|_| AuradineError::Timeout)?
+        .map_err(|e| AuradineError::NetworkError(e.to_string()))
+    }
+
+    /// Get the miner summary
+    pub async fn summary(&self) -> Result<Value, AuradineError> {
+        self.send_command("summary", None, Method::GET).await
+    }
+
+    /// Get per-board data
+    pub async fn devs(&self) -> Result<Value, AuradineError> {
+        self.send_command("devs", None, Method::GET).await
+    }
+
+    /// Get fan speeds
+    pub async fn fan(&self) -> Result<Value, AuradineError> {
+        self.send_command("fan", None, Method::GET).await
+    }
+
+    /// Get PSU input and output readings
+    pub async fn psu(&self) -> Result<Value, AuradineError> {
+        self.send_command("psu", None, Method::GET).await
+    }
+
+    /// Get the LED state
+    pub async fn led(&self) -> Result<Value, AuradineError> {
+        self.send_command("led", None, Method::GET).await
+    }
+
+    /// Get the current power mode
+    pub async fn mode(&self) -> Result<Value, AuradineError> {
+        self.send_command("mode", None, Method::GET).await
+    }
+
+    /// Get the network report, including model and MAC address
+    pub async fn ipreport(&self) -> Result<Value, AuradineError> {
+        self.send_command("ipreport", None, Method::GET).await
+    }
+
+    /// Set the power mode
+    pub async fn set_mode(&self, mode: AuradineMode) -> Result<Value, AuradineError> {
+        let body = match mode {
+            AuradineMode::Eco => json!({"command": "mode", "mode": "eco"}),
+            AuradineMode::Normal => json!({"command": "mode", "mode": "normal"}),
+            AuradineMode::Turbo => json!({"command": "mode", "mode": "turbo"}),
+            AuradineMode::Power(watts) => {
+                json!({"command": "mode", "mode": "custom", "tune": "power", "power": watts})
+            }
+        };
+        self.send_command("mode", Some(body), Method::POST).await
+    }
+
+    /// Turn the locate LED on or off
+    pub async fn set_led(&self, on: bool) -> Result<Value, AuradineError> {
+        // code 1 blinks the LED to locate the miner, 0 returns it to normal
+        let body = json!({"command": "led", "code": if on { 1 } else { 0 }});
+        self.send_command("led", Some(body), Method::POST).await
+    }
+}
+
+/// Native Auradine power modes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuradineMode {
+    Eco,
+    Normal,
+    Turbo,
+    /// A custom mode, tuned to a power target in watts
+    Power(u32),
+}
+
+async fn parse_response(response: Response) -> Result<Value, AuradineError> {
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        return Err(AuradineError::AuthenticationFailed);
+    }
+    if !status.is_success() {
+        return Err(AuradineError::HttpError(status.as_u16()));
+    }
+
+    let json_data: Value = response
+        .json()
+        .await
+        .map_err(|e| AuradineError::ParseError(e.to_string()))?;
+
+    // errors are reported cgminer style, in the STATUS section
+    match json_data["STATUS"][0]["STATUS"].as_str() {
+        Some("E") => {
+            let msg = json_data["STATUS"][0]["Msg"]
+                .as_str()
+                .unwrap_or_default()
+                .to_owned();
+            if msg.to_lowercase().contains("token") {
+                Err(AuradineError::AuthenticationFailed)
+            } else {
+                Err(AuradineError::ApiError(msg))
+            }
+        }
+        _ => Ok(json_data),
+    }
+}
+
+/// Error types for Auradine API operations
+#[derive(Debug, Clone)]
+pub enum AuradineError {
+    /// Network error (connection issues, DNS resolution, etc.)
+    NetworkError(String),
+    /// HTTP error with status code
+    HttpError(u16),
+    /// JSON parsing error
+    ParseError(String),
+    /// The token request failed, or the token was rejected
+    AuthenticationFailed,
+    /// The miner returned an error status
+    ApiError(String),
+    /// Timeout error
+    Timeout,
+    /// Unsupported HTTP method
+    UnsupportedMethod(String),
+}
+
+impl std::fmt::Display for AuradineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuradineError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            AuradineError::HttpError(code) => write!(f, "HTTP error: {}", code),
+            AuradineError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            AuradineError::AuthenticationFailed => write!(f, "Authentication failed"),
+            AuradineError::ApiError(msg) => write!(f, "API error: {}", msg),
+            AuradineError::Timeout => write!(f, "Request timeout"),
+            AuradineError::UnsupportedMethod(method) => {
+                write!(f, "Unsupported method: {}", method)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AuradineError {}
@@ -1,4 +1,5 @@
 use crate::miners::api::ApiClient;
+use crate::miners::api::web::{WebAddress, WebClient, client_builder};
 use async_trait::async_trait;
 use reqwest::{Client, Method, Response};
 use serde_json::Value;
@@ -8,8 +9,7 @@ use tokio::time::timeout;
 /// ESPMiner WebAPI client for communicating with BitAxe and similar miners
 pub struct EspWebApi {
     client: Client,
-    pub ip: String,
-    port: u16,
+    pub address: WebAddress,
     timeout: Duration,
     retries: u32,
 }
@@ -23,18 +23,26 @@ impl ApiClient for EspWebApi {
     }
 }
 
+impl WebClient for EspWebApi {
+    fn address(&self) -> &WebAddress {
+        &self.address
+    }
+
+    fn address_mut(&mut self) -> &mut WebAddress {
+        &mut self.address
+    }
+}
+
 impl EspWebApi {
     /// Create a new ESPMiner WebAPI client
     pub fn new(ip: String, port: u16) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(10))
+        let client = client_builder()
             .build()
             .expect("Failed to create HTTP client");
 
         Self {
             client,
-            ip,
-            port,
+            address: WebAddress::new(ip, port),
             timeout: Duration::from_secs(5),
             retries: 1,
         }
@@ -60,7 +68,7 @@ impl EspWebApi {
         parameters: Option<Value>,
         method: Method,
     ) -> Result<Value, ESPMinerError> {
-        let url = format!("http://{}:{}/api/{}", self.ip, self.port, command);
+        let url = format!("{}/api/{}", self.base_url(), command);
 
         for attempt in 0..=self.retries {
             let result = self
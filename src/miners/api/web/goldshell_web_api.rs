@@ -0,0 +1,215 @@
+use crate::miners::api::ApiClient;
+use crate::miners::api::web::{WebAddress, WebClient, client_builder};
+use async_trait::async_trait;
+use reqwest::{Client, Method, StatusCode};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::timeout;
+
+/// Goldshell WebAPI client, authenticating with a bearer token from `/user/login`
+pub struct GoldshellWebApi {
+    client: Client,
+    pub address: WebAddress,
+    username: String,
+    password: String,
+    token: RwLock<Option<String>>,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApiClient for GoldshellWebApi {
+    async fn send_command(&self, command: &'static str) -> Result<Value, String> {
+        self.send_command(command, None, Method::GET)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl WebClient for GoldshellWebApi {
+    fn address(&self) -> &WebAddress {
+        &self.address
+    }
+
+    fn address_mut(&mut self) -> &mut WebAddress {
+        &mut self.address
+    }
+}
+
+impl GoldshellWebApi {
+    /// Create a new Goldshell WebAPI client with the default credentials
+    pub fn new(ip: String, port: u16) -> Self {
+        let client = client_builder()
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            address: WebAddress::new(ip, port),
+            username: String::from("admin"),
+            password: String::from("123456789"),
+            token: RwLock::new(None),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Set the timeout for API requests
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the credentials used to log in to the web interface
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.username = username.to_owned();
+        self.password = password.to_owned();
+        self
+    }
+
+    /// Log in and store a new bearer token
+    async fn login(&self) -> Result<String, GoldshellError> {
+        let url = format!("{}/user/login", self.base_url());
+        let request = self
+            .client
+            .get(url)
+            .query(&[
+                ("username", self.username.as_str()),
+                ("password", self.password.as_str()),
+                ("cipher", "false"),
+            ])
+            .timeout(self.timeout);
+
+        let response = timeout(self.timeout, request.send())
+            .await
+            .map_err(|_| GoldshellError::Timeout)?
+            .map_err(|e| GoldshellError::NetworkError(e.to_string()))?;
+        let json_data: Value = response
+            .json()
+            .await
+            .map_err(|e| GoldshellError::ParseError(e.to_string()))?;
+
+        let token = json_data["JWT Token"]
+            .as_str()
+            .ok_or(GoldshellError::AuthenticationFailed)?
+            .to_owned();
+        *self.token.write().await = Some(token.clone());
+
+        Ok(token)
+    }
+
+    /// Send a command to the miner, logging in first if needed
+    pub async fn send_command(
+        &self,
+        command: &str,
+        parameters: Option<Value>,
+        method: Method,
+    ) -> Result<Value, GoldshellError> {
+        let url = format!("{}/mcb/{}", self.base_url(), command);
+
+        let token = match self.token.read().await.clone() {
+            Some(token) => token,
+            None => self.login().await?,
+        };
+
+        let mut response = self
+            .execute_request(&url, &method, parameters.clone(), &token)
+            .await?;
+        // tokens expire, log in again once and retry
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let token = self.login().await?;
+            response = self
+                .execute_request(&url, &method, parameters, &token)
+                .await?;
+        }
+
+        if !response.status().is_success() {
+            return Err(GoldshellError::HttpError(response.status().as_u16()));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| GoldshellError::ParseError(e.to_string()))
+    }
+
+    async fn execute_request(
+        &self,
+        url: &str,
+        method: &Method,
+        parameters: Option<Value>,
+        token: &str,
+    ) -> Result<reqwest::Response, GoldshellError> {
+        let mut builder = match *method {
+            Method::GET => self.client.get(url),
+            Method::PUT => self.client.put(url),
+            Method::POST => self.client.post(url),
+            Method::DELETE => self.client.delete(url),
+            _ => return Err(GoldshellError::UnsupportedMethod(method.to_string())),
+        };
+        if let Some(params) = parameters {
+            builder = builder.json(&params);
+        }
+
+        timeout(
+            self.timeout,
+            builder.bearer_auth(token).timeout(self.timeout).send(),
+        )
+        .await
+        .map_err(|_| GoldshellError::Timeout)?
+        .map_err(|e| GoldshellError::NetworkError(e.to_string()))
+    }
+
+    /// Get the miner status, including model and firmware
+    pub async fn status(&self) -> Result<Value, GoldshellError> {
+        self.send_command("status", None, Method::GET).await
+    }
+
+    /// Get the configured pools
+    pub async fn pools(&self) -> Result<Value, GoldshellError> {
+        self.send_command("pools", None, Method::GET).await
+    }
+
+    /// Get per-board data from the CGMiner `devs` command
+    pub async fn devs(&self) -> Result<Value, GoldshellError> {
+        self.send_command("cgminer?cgminercmd=devs", None, Method::GET)
+            .await
+    }
+
+    /// Get the miner settings
+    pub async fn setting(&self) -> Result<Value, GoldshellError> {
+        self.send_command("setting", None, Method::GET).await
+    }
+}
+
+/// Error types for Goldshell WebAPI operations
+#[derive(Debug, Clone)]
+pub enum GoldshellError {
+    /// Network error (connection issues, DNS resolution, etc.)
+    NetworkError(String),
+    /// HTTP error with status code
+    HttpError(u16),
+    /// JSON parsing error
+    ParseError(String),
+    /// Login did not return a token
+    AuthenticationFailed,
+    /// Timeout error
+    Timeout,
+    /// Unsupported HTTP method
+    UnsupportedMethod(String),
+}
+
+impl std::fmt::Display for GoldshellError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GoldshellError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            GoldshellError::HttpError(code) => write!(f, "HTTP error: {}", code),
+            GoldshellError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            GoldshellError::AuthenticationFailed => write!(f, "Authentication failed"),
+            GoldshellError::Timeout => write!(f, "Request timeout"),
+            GoldshellError::UnsupportedMethod(method) => {
+                write!(f, "Unsupported method: {}", method)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GoldshellError {}
@@ -0,0 +1,226 @@
+use crate::miners::api::ApiClient;
+use crate::miners::api::web::{WebAddress, WebClient, client_builder};
+use async_trait::async_trait;
+use reqwest::header::{COOKIE, SET_COOKIE};
+use reqwest::{Client, Response};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::timeout;
+
+/// IceRiver WebAPI client, authenticating with a session cookie from `/user/loginpost`
+pub struct IceRiverWebApi {
+    client: Client,
+    pub address: WebAddress,
+    username: String,
+    password: String,
+    session: RwLock<Option<String>>,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApiClient for IceRiverWebApi {
+    async fn send_command(&self, command: &'static str) -> Result<Value, String> {
+        self.send_command(command, &[])
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl WebClient for IceRiverWebApi {
+    fn address(&self) -> &WebAddress {
+        &self.address
+    }
+
+    fn address_mut(&mut self) -> &mut WebAddress {
+        &mut self.address
+    }
+}
+
+impl IceRiverWebApi {
+    /// Create a new IceRiver WebAPI client with the default credentials
+    pub fn new(ip: String, port: u16) -> Self {
+        let client = client_builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            address: WebAddress::new(ip, port),
+            username: String::from("admin"),
+            password: String::from("12345678"),
+            session: RwLock::new(None),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Set the timeout for API requests
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the credentials used to log in to the web interface
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.username = username.to_owned();
+        self.password = password.to_owned();
+        self
+    }
+
+    /// Log in and store the new session cookie
+    async fn login(&self) -> Result<String, IceRiverError> {
+        let url = format!("{}/user/loginpost", self.base_url());
+        let request = self
+            .client
+            .post(url)
+            .form(&[
+                ("post", "6"),
+                ("user", self.username.as_str()),
+                ("pwd", self.password.as_str()),
+            ])
+            .timeout(self.timeout);
+
+        let response = timeout(self.timeout, request.send())
+            .await
+            .map_err(|_| IceRiverError::Timeout)?
+            .map_err(|e| IceRiverError::NetworkError(e.to_string()))?;
+
+        // the session is only in the cookie, keep the `name=value` part of each one
+        let session = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|cookie| cookie.split(';').next())
+            .collect::<Vec<&str>>()
+            .join("; ");
+        if session.is_empty() {
+            return Err(IceRiverError::AuthenticationFailed);
+        }
+        *self.session.write().await = Some(session.clone());
+
+        Ok(session)
+    }
+
+    /// Send a command to the miner, logging in first if needed.
+    ///
+    /// Commands are paths under `/user/`, and are sent as a form POST.
+    pub async fn send_command(
+        &self,
+        command: &str,
+        parameters: &[(&str, &str)],
+    ) -> Result<Value, IceRiverError> {
+        let url = format!("{}/user/{}", self.base_url(), command);
+        let mut form = vec![("post", command_post_id(command))];
+        form.extend_from_slice(parameters);
+
+        let session = match self.session.read().await.clone() {
+            Some(session) => session,
+            None => self.login().await?,
+        };
+
+        let response = self.execute_request(&url, &form, &session).await?;
+        let json_data = match parse_response(response).await {
+            // sessions expire, the miner then redirects to the login page instead of
+            // returning JSON, so log in again once and retry
+            Err(IceRiverError::SessionExpired) => {
+                let session = self.login().await?;
+                let response = self.execute_request(&url, &form, &session).await?;
+                parse_response(response).await?
+            }
+            result => result?,
+        };
+
+        match json_data["error"].as_i64() {
+            Some(0) | None => Ok(json_data),
+            Some(code) => Err(IceRiverError::ApiError(code)),
+        }
+    }
+
+    async fn execute_request(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+        session: &str,
+    ) -> Result<Response, IceRiverError> {
+        let request = self
+            .client
+            .post(url)
+            .header(COOKIE, session)
+            .form(form)
+            .timeout(self.timeout);
+
+        timeout(self.timeout, request.send())
+            .await
+            .map_err(|_| IceRiverError::Timeout)?
+            .map_err(|e| IceRiverError::NetworkError(e.to_string()))
+    }
+
+    /// Get the user panel, containing nearly all miner data
+    pub async fn userpanel(&self) -> Result<Value, IceRiverError> {
+        self.send_command("userpanel", &[]).await
+    }
+}
+
+/// The value of the `post` form field the web UI sends with each page
+fn command_post_id(command: &str) -> &'static str {
+    match command {
+        "userpanel" => "4",
+        _ => "1",
+    }
+}
+
+async fn parse_response(response: Response) -> Result<Value, IceRiverError> {
+    let status = response.status();
+    if status.is_redirection() {
+        return Err(IceRiverError::SessionExpired);
+    }
+    if !status.is_success() {
+        return Err(IceRiverError::HttpError(status.as_u16()));
+    }
+
+    let text = response
+        .text()
+        .await
+        .map_err(|e| IceRiverError::ParseError(e.to_string()))?;
+    if text.trim_start().starts_with('<') {
+        return Err(IceRiverError::SessionExpired);
+    }
+    serde_json::from_str(&text).map_err(|e| IceRiverError::ParseError(e.to_string()))
+}
+
+/// Error types for IceRiver WebAPI operations
+#[derive(Debug, Clone)]
+pub enum IceRiverError {
+    /// Network error (connection issues, DNS resolution, etc.)
+    NetworkError(String),
+    /// HTTP error with status code
+    HttpError(u16),
+    /// JSON parsing error
+    ParseError(String),
+    /// Login did not return a session cookie
+    AuthenticationFailed,
+    /// The session cookie is no longer valid
+    SessionExpired,
+    /// The miner returned a non-zero error code
+    ApiError(i64),
+    /// Timeout error
+    Timeout,
+}
+
+impl std::fmt::Display for IceRiverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IceRiverError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            IceRiverError::HttpError(code) => write!(f, "HTTP error: {}", code),
+            IceRiverError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            IceRiverError::AuthenticationFailed => write!(f, "Authentication failed"),
+            IceRiverError::SessionExpired => write!(f, "Session expired"),
+            IceRiverError::ApiError(code) => write!(f, "API error code: {}", code),
+            IceRiverError::Timeout => write!(f, "Request timeout"),
+        }
+    }
+}
+
+impl std::error::Error for IceRiverError {}
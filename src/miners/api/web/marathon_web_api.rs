@@ -0,0 +1,199 @@
+use crate::miners::api::ApiClient;
+use crate::miners::api::web::{WebAddress, WebClient, client_builder};
+use async_trait::async_trait;
+use diqwest::WithDigestAuth;
+use reqwest::{Client, Method};
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Marathon (MARA) firmware REST API client, using digest auth on `/kaonsu/v1/`
+pub struct MarathonWebApi {
+    client: Client,
+    pub address: WebAddress,
+    username: String,
+    password: String,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApiClient for MarathonWebApi {
+    async fn send_command(&self, command: &'static str) -> Result<Value, String> {
+        self.send_command(command, None, Method::GET)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl WebClient for MarathonWebApi {
+    fn address(&self) -> &WebAddress {
+        &self.address
+    }
+
+    fn address_mut(&mut self) -> &mut WebAddress {
+        &mut self.address
+    }
+}
+
+impl MarathonWebApi {
+    /// Create a new Marathon API client with the default credentials
+    pub fn new(ip: String, port: u16) -> Self {
+        let client = client_builder()
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            address: WebAddress::new(ip, port),
+            username: String::from("root"),
+            password: String::from("root"),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Set the timeout for API requests
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the credentials used for digest auth
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.username = username.to_owned();
+        self.password = password.to_owned();
+        self
+    }
+
+    /// Send a command to the miner
+    pub async fn send_command(
+        &self,
+        command: &str,
+        parameters: Option<Value>,
+        method: Method,
+    ) -> Result<Value, MarathonError> {
+        let url = format!("{}/kaonsu/v1/{}", self.base_url(), command);
+
+        let mut builder = match method {
+            Method::GET => self.client.get(url),
+            Method::POST => self.client.post(url),
+            _ => return Err(MarathonError::UnsupportedMethod(method.to_string())),
+        };
+        if let Some(params) = parameters {
+            builder = builder.json(&params);
+        }
+        let builder = builder.timeout(self.timeout);
+
+        let response = timeout(
+            self.timeout,
+            builder.send_with_digest_auth(&self.username, &self.password),
+        )
+        .await
+        .map_err(|_| MarathonError::Timeout)?
+        .map_err(|e| MarathonError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(MarathonError::HttpError(response.status().as_u16()));
+        }
+        let text = response
+            .text()
+            .await
+            .map_err(|e| MarathonError::ParseError(e.to_string()))?;
+        // write commands can reply with an empty body
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&text).map_err(|e| MarathonError::ParseError(e.to_string()))
+    }
+
+    /// Get the current hashrate, power and status
+    pub async fn brief(&self) -> Result<Value, MarathonError> {
+        self.send_command("brief", None, Method::GET).await
+    }
+
+    /// Get the model, serial number and firmware version
+    pub async fn overview(&self) -> Result<Value, MarathonError> {
+        self.send_command("overview", None, Method::GET).await
+    }
+
+    /// Get per-board hashrate, temperatures and chip counts
+    pub async fn hashboards(&self) -> Result<Value, MarathonError> {
+        self.send_command("hashboards", None, Method::GET).await
+    }
+
+    /// Get fan speeds
+    pub async fn fans(&self) -> Result<Value, MarathonError> {
+        self.send_command("fans", None, Method::GET).await
+    }
+
+    /// Get the configured pools and their share counts
+    pub async fn pools(&self) -> Result<Value, MarathonError> {
+        self.send_command("pools", None, Method::GET).await
+    }
+
+    /// Get the network configuration, including MAC address and hostname
+    pub async fn network_config(&self) -> Result<Value, MarathonError> {
+        self.send_command("network_config", None, Method::GET).await
+    }
+
+    /// Get the miner configuration, including fan and tuning settings
+    pub async fn miner_config(&self) -> Result<Value, MarathonError> {
+        self.send_command("miner_config", None, Method::GET).await
+    }
+
+    /// Get whether the locate LED is blinking
+    pub async fn locate_miner(&self) -> Result<Value, MarathonError> {
+        self.send_command("locate_miner", None, Method::GET).await
+    }
+
+    /// Start or stop blinking the locate LED
+    pub async fn set_locate_miner(&self, blinking: bool) -> Result<Value, MarathonError> {
+        self.send_command(
+            "locate_miner",
+            Some(json!({ "blinking": blinking })),
+            Method::POST,
+        )
+        .await
+    }
+
+    /// Replace the configured pools, a list of `{"url", "user", "pass"}` objects
+    pub async fn set_pools(&self, pools: Value) -> Result<Value, MarathonError> {
+        self.send_command("pools", Some(pools), Method::POST).await
+    }
+
+    /// Update the miner configuration, such as the fan or tuning mode
+    pub async fn set_miner_config(&self, config: Value) -> Result<Value, MarathonError> {
+        self.send_command("miner_config", Some(config), Method::POST)
+            .await
+    }
+}
+
+/// Error types for Marathon API operations
+#[derive(Debug, Clone)]
+pub enum MarathonError {
+    /// Network error (connection issues, DNS resolution, authentication, etc.)
+    NetworkError(String),
+    /// HTTP error with status code
+    HttpError(u16),
+    /// JSON parsing error
+    ParseError(String),
+    /// Timeout error
+    Timeout,
+    /// Unsupported HTTP method
+    UnsupportedMethod(String),
+}
+
+impl std::fmt::Display for MarathonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarathonError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            MarathonError::HttpError(code) => write!(f, "HTTP error: {}", code),
+            MarathonError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            MarathonError::Timeout => write!(f, "Request timeout"),
+            MarathonError::UnsupportedMethod(method) => {
+                write!(f, "Unsupported method: {}", method)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarathonError {}
@@ -1 +1,89 @@
+pub mod auradine_web_api;
 pub mod esp_web_api;
+pub mod goldshell_web_api;
+pub mod iceriver_web_api;
+pub mod marathon_web_api;
+
+use reqwest::ClientBuilder;
+use std::net::Ipv6Addr;
+use std::time::Duration;
+
+/// Where a web API client reaches a miner's web server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebAddress {
+    pub ip: String,
+    pub port: u16,
+    pub https: bool,
+}
+
+impl WebAddress {
+    pub fn new(ip: String, port: u16) -> Self {
+        Self {
+            ip,
+            port,
+            https: false,
+        }
+    }
+
+    /// The root URL of the web server, such as `https://10.0.0.5:8443`
+    pub fn base_url(&self) -> String {
+        let scheme = match self.https {
+            true => "https",
+            false => "http",
+        };
+        // IPv6 literals are bracketed so the port is not read as part of the address
+        match self.ip.parse::<Ipv6Addr>() {
+            Ok(_) => format!("{}://[{}]:{}", scheme, self.ip, self.port),
+            Err(_) => format!("{}://{}:{}", scheme, self.ip, self.port),
+        }
+    }
+}
+
+/// A web API client, reachable over HTTP or HTTPS
+pub trait WebClient: Sized {
+    fn address(&self) -> &WebAddress;
+
+    fn address_mut(&mut self) -> &mut WebAddress;
+
+    /// Use HTTPS instead of HTTP, accepting the self-signed certificates miners ship with
+    fn with_https(mut self, https: bool) -> Self {
+        self.address_mut().https = https;
+        self
+    }
+
+    /// The root URL of the web server
+    fn base_url(&self) -> String {
+        self.address().base_url()
+    }
+}
+
+/// The HTTP client settings shared by the web API clients
+pub(crate) fn client_builder() -> ClientBuilder {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .danger_accept_invalid_certs(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::iceriver_web_api::IceRiverWebApi;
+    use super::*;
+
+    #[test]
+    fn test_https_base_url() {
+        let client = IceRiverWebApi::new(String::from("10.0.0.5"), 8443).with_https(true);
+        assert_eq!(client.base_url(), "https://10.0.0.5:8443");
+
+        let client = IceRiverWebApi::new(String::from("10.0.0.5"), 8080);
+        assert_eq!(client.base_url(), "http://10.0.0.5:8080");
+    }
+
+    #[test]
+    fn test_ipv6_base_url() {
+        let client = IceRiverWebApi::new(String::from("fd00::5"), 8443).with_https(true);
+        assert_eq!(client.base_url(), "https://[fd00::5]:8443");
+
+        let client = IceRiverWebApi::new(String::from("miner01.site"), 80);
+        assert_eq!(client.base_url(), "http://miner01.site:80");
+    }
+}
@@ -0,0 +1,546 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use macaddr::MacAddr;
+use measurements::{AngularVelocity, Frequency, Power, Temperature, Voltage};
+use serde_json::Value;
+
+use crate::data::board::BoardData;
+use crate::data::device::MinerMake::Auradine as AuradineMake;
+use crate::data::device::{DeviceInfo, HashAlgorithm, MinerFirmware, MinerHardware, MinerModel};
+use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
+use crate::data::hashrate::{HashRate, HashRateUnit};
+use crate::data::miner::MinerData;
+use crate::data::pool::{PoolData, PoolURL};
+use crate::miners::api::web::WebClient;
+use crate::miners::api::web::auradine_web_api::{AuradineError, AuradineMode, AuradineWebApi};
+use crate::miners::backends::traits::{GetMinerData, RawCommandError, raw_web_method};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{
+    DataCollector, DataExtensions, DataExtractor, DataField, DataLocation, get_by_pointer,
+};
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
+
+/// The port the Auradine REST API listens on
+const API_PORT: u16 = 8080;
+
+/// Auradine Teraflux backend, using the REST API on port 8080
+pub struct Auradine {
+    model: MinerModel,
+    web: AuradineWebApi,
+    ip: IpAddr,
+    target: MinerTarget,
+}
+
+impl Auradine {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(target: impl Into<MinerTarget>, model: MinerModel) -> Self {
+        let target = target.into();
+        let ports = target.ports(MinerPorts::new());
+        Auradine {
+            model,
+            web: Self::web_api(target.host(), ports),
+            ip: target.data_ip(),
+            target,
+        }
+        .with_ports(ports)
+    }
+
+    /// Use non-standard ports or HTTPS for the web API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.web = Self::web_api(self.target.host(), ports);
+        self
+    }
+
+    /// The REST API client for a host, which stays on port 8080 over HTTPS unless remapped
+    pub(crate) fn web_api(host: &str, ports: MinerPorts) -> AuradineWebApi {
+        let port = match ports.use_https {
+            true => ports.https.unwrap_or(API_PORT),
+            false => ports.http.unwrap_or(API_PORT),
+        };
+        AuradineWebApi::new(host.to_owned(), port).with_https(ports.use_https)
+    }
+
+    /// Whether this miner is an immersion cooled model
+    pub fn is_immersion(&self) -> bool {
+        matches!(&self.model, MinerModel::Auradine(model) if model.is_immersion())
+    }
+}
+
+/// Controls specific to Auradine miners.
+///
+/// A miner from the factory exposes these through [`GetMinerData::as_auradine`].
+#[async_trait]
+pub trait AuradineControl: Send + Sync {
+    /// Get the current native power mode
+    async fn power_mode(&self) -> Result<Option<AuradineMode>, AuradineError>;
+
+    /// Switch to one of the native power modes
+    async fn set_power_mode(&self, mode: AuradineMode) -> Result<(), AuradineError>;
+
+    /// Turn the locate LED on or off
+    async fn set_fault_light(&self, on: bool) -> Result<(), AuradineError>;
+}
+
+#[async_trait]
+impl AuradineControl for Auradine {
+    async fn power_mode(&self) -> Result<Option<AuradineMode>, AuradineError> {
+        let mode = self.web.mode().await?;
+        let mode = &mode["Mode"][0];
+
+        Ok(match mode["Mode"].as_str() {
+            Some("eco") => Some(AuradineMode::Eco),
+            Some("normal") => Some(AuradineMode::Normal),
+            Some("turbo") => Some(AuradineMode::Turbo),
+            Some("custom") => parse_watts(&mode["Power"]).map(|w| AuradineMode::Power(w as u32)),
+            _ => None,
+        })
+    }
+
+    async fn set_power_mode(&self, mode: AuradineMode) -> Result<(), AuradineError> {
+        self.web.set_mode(mode).await.map(|_| ())
+    }
+
+    async fn set_fault_light(&self, on: bool) -> Result<(), AuradineError> {
+        self.web.set_led(on).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl GetMinerData for Auradine {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut collector = DataCollector::new(self, &self.web);
+        let data = collector.collect(fields).await;
+
+        let miner_hardware = MinerHardware::from(&self.model);
+
+        let mac = data
+            .extract::<String>(DataField::Mac)
+            .and_then(|s| MacAddr::from_str(&s).ok());
+        let hostname = data.extract::<String>(DataField::Hostname);
+        let firmware_version = data
+            .extract::<String>(DataField::FirmwareVersion)
+            .map(|v| FirmwareVersion::parse(MinerFirmware::Stock, &v));
+        let uptime = data.extract_map::<u64, _>(DataField::Uptime, Duration::from_secs);
+        let light_flashing = data.extract::<bool>(DataField::LightFlashing);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get system time")
+            .as_secs();
+
+        let hashrate = data.extract_map::<f64, _>(DataField::Hashrate, |f| HashRate {
+            value: f,
+            unit: HashRateUnit::MegaHash,
+            algo: String::from("SHA256"),
+        });
+
+        let hashboards: Vec<BoardData> = data
+            .get(&DataField::Hashboards)
+            .and_then(|v| v.as_array())
+            .map(|boards| {
+                boards
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, board)| parse_board(idx, board, miner_hardware.chips))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let fans = data
+            .get(&DataField::Fans)
+            .and_then(|v| v.as_array())
+            .map(|fans| {
+                fans.iter()
+                    .enumerate()
+                    .filter_map(|(idx, fan)| {
+                        Some(FanData {
+                            position: fan["ID"].as_i64().unwrap_or(idx as i64) as i16,
+                            rpm: AngularVelocity::from_rpm(fan["Speed"].as_f64()?),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let average_temperature = {
+            let temps: Vec<f64> = hashboards
+                .iter()
+                .filter_map(|b| b.board_temperature)
+                .map(|t| t.as_celsius())
+                .collect();
+            if temps.is_empty() {
+                None
+            } else {
+                Some(Temperature::from_celsius(
+                    temps.iter().sum::<f64>() / temps.len() as f64,
+                ))
+            }
+        };
+
+        // immersion units report the fluid temperature directly, if they don't, the board
+        // intake temperature is the temperature of the fluid coming in
+        let fluid_temperature = data
+            .extract_map::<f64, _>(DataField::FluidTemperature, Temperature::from_celsius)
+            .or_else(|| {
+                if !self.is_immersion() {
+                    return None;
+                }
+                let temps: Vec<f64> = hashboards
+                    .iter()
+                    .filter_map(|b| b.intake_temperature)
+                    .map(|t| t.as_celsius())
+                    .collect();
+                if temps.is_empty() {
+                    None
+                } else {
+                    Some(Temperature::from_celsius(
+                        temps.iter().sum::<f64>() / temps.len() as f64,
+                    ))
+                }
+            });
+
+        let wattage = data
+            .get(&DataField::Wattage)
+            .and_then(|v| parse_watts(v))
+            .map(Power::from_watts);
+        let wattage_limit = data
+            .get(&DataField::WattageLimit)
+            .and_then(|v| parse_watts(v))
+            .map(Power::from_watts);
+        let efficiency = match (&hashrate, wattage) {
+            (Some(hr), Some(w)) if hr.value > 0.0 => Some(w / hr.as_unit(HashRateUnit::TeraHash)),
+            _ => None,
+        };
+
+        let pools = data
+            .get(&DataField::Pools)
+            .and_then(|v| v.as_array())
+            .map(|pools| pools.iter().enumerate().map(parse_pool).collect())
+            .unwrap_or_default();
+
+        let total_chips = hashboards
+            .iter()
+            .map(|b| b.working_chips)
+            .sum::<Option<u16>>();
+        let is_mining = hashrate.as_ref().is_some_and(|hr| hr.value > 0.0);
+
+        MinerData {
+            schema_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp,
+            ip: self.ip,
+            target_name: self.target.hostname().map(String::from),
+            mac,
+            device_info: DeviceInfo::new(
+                AuradineMake,
+                self.model.clone(),
+                MinerFirmware::Stock,
+                HashAlgorithm::SHA256,
+            ),
+            serial_number: None,
+            hostname,
+            api_version: None,
+            firmware_version,
+            control_board_version: None,
+            expected_hashboards: miner_hardware.boards,
+            hashboards,
+            hashrate,
+            expected_chips: match (miner_hardware.chips, miner_hardware.boards) {
+                (Some(chips), Some(boards)) => Some(chips * boards as u16),
+                _ => None,
+            },
+            total_chips,
+            expected_fans: miner_hardware.fans,
+            fans,
+            psu_fans: vec![],
+            average_temperature,
+            fluid_temperature,
+            wattage,
+            wattage_limit,
+            efficiency,
+            light_flashing,
+            messages: vec![],
+            uptime,
+            is_mining,
+            pools,
+            location: None,
+        }
+    }
+
+    fn as_auradine(&self) -> Option<&dyn AuradineControl> {
+        Some(self)
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        const SUMMARY_CMD: &str = "summary";
+        const DEVS_CMD: &str = "devs";
+        const FAN_CMD: &str = "fan";
+        const PSU_CMD: &str = "psu";
+        const LED_CMD: &str = "led";
+        const MODE_CMD: &str = "mode";
+        const IPREPORT_CMD: &str = "ipreport";
+        const POOLS_CMD: &str = "pools";
+
+        match data_field {
+            DataField::Mac => &[(
+                IPREPORT_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/IPReport/0/mac"),
+                },
+            )],
+            DataField::Hostname => &[(
+                IPREPORT_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/IPReport/0/hostname"),
+                },
+            )],
+            DataField::FirmwareVersion => &[(
+                IPREPORT_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/IPReport/0/version"),
+                },
+            )],
+            DataField::Hashrate => &[(
+                SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/SUMMARY/0/MHS 5s"),
+                },
+            )],
+            DataField::Uptime => &[(
+                SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/SUMMARY/0/Elapsed"),
+                },
+            )],
+            DataField::FluidTemperature => &[(
+                SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/SUMMARY/0/Fluid Temperature"),
+                },
+            )],
+            DataField::Hashboards => &[(
+                DEVS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/DEVS"),
+                },
+            )],
+            DataField::Fans => &[(
+                FAN_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/Fan"),
+                },
+            )],
+            DataField::Wattage => &[(
+                PSU_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/PSU/0/PowerIn"),
+                },
+            )],
+            DataField::WattageLimit => &[(
+                MODE_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/Mode/0/Power"),
+                },
+            )],
+            DataField::LightFlashing => &[(
+                LED_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/LED/0/Code"),
+                },
+            )],
+            DataField::Pools => &[(
+                POOLS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/POOLS"),
+                },
+            )],
+            _ => &[],
+        }
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::WebAPI { command } => {
+                let method = raw_web_method(&params);
+                self.web
+                    .send_command(command, params, method)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
+    }
+}
+
+/// Parse a power reading, which is either a number or a string such as `"3250W"`
+fn parse_watts(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| {
+        value
+            .as_str()?
+            .trim()
+            .trim_end_matches(['W', 'w'])
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
+fn parse_board(idx: usize, board: &Value, expected_chips: Option<u16>) -> BoardData {
+    let hashrate = board["MHS 5s"].as_f64().map(|f| HashRate {
+        value: f,
+        unit: HashRateUnit::MegaHash,
+        algo: String::from("SHA256"),
+    });
+
+    BoardData {
+        position: board["ID"].as_u64().unwrap_or(idx as u64) as u8,
+        active: Some(hashrate.as_ref().is_some_and(|hr| hr.value > 0.0)),
+        hashrate,
+        expected_hashrate: None,
+        board_temperature: board["Temperature"].as_f64().map(Temperature::from_celsius),
+        intake_temperature: board["Intake Temperature"]
+            .as_f64()
+            .map(Temperature::from_celsius),
+        outlet_temperature: board["Outlet Temperature"]
+            .as_f64()
+            .map(Temperature::from_celsius),
+        expected_chips,
+        working_chips: board["Chip Count"].as_u64().map(|u| u as u16),
+        serial_number: board["Serial Number"].as_str().map(String::from),
+        chips: vec![],
+        voltage: board["Voltage"].as_f64().map(Voltage::from_volts),
+        frequency: board["Frequency"].as_f64().map(Frequency::from_megahertz),
+        tuned: None,
+    }
+}
+
+fn parse_pool((idx, pool): (usize, &Value)) -> PoolData {
+    PoolData {
+        position: Some(pool["POOL"].as_u64().unwrap_or(idx as u64) as u16),
+        url: pool["URL"].as_str().and_then(PoolURL::parse),
+        accepted_shares: pool["Accepted"].as_u64(),
+        rejected_shares: pool["Rejected"].as_u64(),
+        active: pool["Stratum Active"].as_bool(),
+        alive: pool["Status"].as_str().map(|s| s == "Alive"),
+        user: pool["User"].as_str().map(String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::device::models::auradine::AuradineModel;
+    use crate::miners::target::NamedMiner;
+    use serde_json::json;
+
+    #[test]
+    fn test_web_api_port() {
+        let web = Auradine::web_api("10.0.0.5", MinerPorts::new());
+        assert_eq!(web.base_url(), "http://10.0.0.5:8080");
+
+        // discovery found HTTPS on the web UI, the REST API stays on its own port
+        let web = Auradine::web_api("10.0.0.5", MinerPorts::new().with_use_https(true));
+        assert_eq!(web.base_url(), "https://10.0.0.5:8080");
+
+        let web = Auradine::web_api("fd00::5", MinerPorts::new().with_http(18080));
+        assert_eq!(web.base_url(), "http://[fd00::5]:18080");
+    }
+
+    #[test]
+    fn test_parse_board() {
+        // a devs response in the shape AT2880 firmware returns
+        let devs = json!({
+            "STATUS": [{"STATUS": "S", "When": 1718000000, "Code": 9, "Msg": "3 ASC(s)"}],
+            "DEVS": [{
+                "ASC": 0, "Name": "Hashboard", "ID": 1, "Enabled": "Y", "Status": "Alive",
+                "Temperature": 55.5, "Intake Temperature": 38.0, "Outlet Temperature": 62.25,
+                "MHS av": 87512345.0, "MHS 5s": 88012345.0, "Chip Count": 132,
+                "Voltage": 14.2, "Frequency": 455.0, "Serial Number": "ADHB2401A0012"
+            }]
+        });
+
+        let board = parse_board(0, &devs["DEVS"][0], Some(132));
+
+        assert_eq!(board.position, 1);
+        assert_eq!(board.active, Some(true));
+        assert_eq!(board.hashrate.map(|hr| hr.value), Some(88012345.0));
+        assert_eq!(
+            board.board_temperature,
+            Some(Temperature::from_celsius(55.5))
+        );
+        assert_eq!(
+            board.intake_temperature,
+            Some(Temperature::from_celsius(38.0))
+        );
+        assert_eq!(
+            board.outlet_temperature,
+            Some(Temperature::from_celsius(62.25))
+        );
+        assert_eq!(board.working_chips, Some(132));
+        assert_eq!(board.serial_number.as_deref(), Some("ADHB2401A0012"));
+        assert_eq!(board.voltage, Some(Voltage::from_volts(14.2)));
+        assert_eq!(board.frequency, Some(Frequency::from_megahertz(455.0)));
+    }
+
+    #[test]
+    fn test_parse_pool() {
+        let pools = json!({
+            "STATUS": [{"STATUS": "S", "Msg": "1 Pool(s)"}],
+            "POOLS": [{
+                "POOL": 0, "URL": "stratum+tcp://stratum.example.com:3333", "Status": "Alive",
+                "Stratum Active": true, "User": "worker.1", "Accepted": 1520, "Rejected": 3
+            }]
+        });
+
+        let pool = parse_pool((0, &pools["POOLS"][0]));
+
+        assert_eq!(pool.position, Some(0));
+        assert!(pool.url.is_some());
+        assert_eq!(pool.accepted_shares, Some(1520));
+        assert_eq!(pool.rejected_shares, Some(3));
+        assert_eq!(pool.active, Some(true));
+        assert_eq!(pool.alive, Some(true));
+        assert_eq!(pool.user.as_deref(), Some("worker.1"));
+    }
+
+    #[test]
+    fn test_parse_watts() {
+        let psu = json!({"PSU": [{"ID": 0, "PowerIn": "3250W", "Temperature": 41.0}]});
+        let mode = json!({"Mode": [{"Mode": "custom", "Power": 3000}]});
+
+        assert_eq!(parse_watts(&psu["PSU"][0]["PowerIn"]), Some(3250.0));
+        assert_eq!(parse_watts(&mode["Mode"][0]["Power"]), Some(3000.0));
+        assert_eq!(parse_watts(&json!("n/a")), None);
+    }
+
+    #[test]
+    fn test_as_auradine() {
+        let miner: Box<dyn GetMinerData> = Box::new(Auradine::new(
+            IpAddr::from([10, 0, 0, 1]),
+            MinerModel::Auradine(AuradineModel::AT2880),
+        ));
+        let named = NamedMiner::new("miner-1", miner);
+
+        assert!(named.as_auradine().is_some());
+    }
+}
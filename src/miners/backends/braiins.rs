@@ -0,0 +1,439 @@
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use measurements::{AngularVelocity, Frequency, Power, Temperature, Voltage};
+use serde_json::Value;
+
+use crate::data::board::BoardData;
+use crate::data::device::MinerMake::Braiins;
+use crate::data::device::{DeviceInfo, HashAlgorithm, MinerFirmware, MinerHardware, MinerModel};
+use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
+use crate::data::hashrate::{HashRate, HashRateUnit};
+use crate::data::miner::MinerData;
+use crate::data::pool::{PoolData, PoolURL};
+use crate::miners::api::rpc::cgminer::CGMinerRPC;
+use crate::miners::api::rpc::traits::SendRPCCommand;
+use crate::miners::backends::traits::{GetMinerData, RawCommandError};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{
+    DataCollector, DataExtensions, DataExtractor, DataField, DataLocation, get_by_pointer,
+};
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
+
+const DEVS_CMD: &str = "devs";
+const TEMPS_CMD: &str = "temps";
+const TUNERSTATUS_CMD: &str = "tunerstatus";
+
+/// Backend for the Braiins Mini Miner (BMM100/BMM101), a single board miner running
+/// Braiins OS, using the BOSminer RPC API on port 4028
+pub struct BraiinsMini {
+    model: MinerModel,
+    rpc: CGMinerRPC,
+    ip: IpAddr,
+    target: MinerTarget,
+}
+
+impl BraiinsMini {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(target: impl Into<MinerTarget>, model: MinerModel) -> Self {
+        let target = target.into();
+        BraiinsMini {
+            model,
+            rpc: CGMinerRPC::new(target.host(), None),
+            ip: target.data_ip(),
+            target,
+        }
+    }
+
+    /// Use a non-standard port for the RPC API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.rpc = CGMinerRPC::new(self.target.host(), Some(ports.rpc_port()));
+        self
+    }
+}
+
+#[async_trait]
+impl GetMinerData for BraiinsMini {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut collector = DataCollector::new(self, &self.rpc);
+        collector.fetch(fields).await;
+        let data = collector.extract(fields);
+
+        let miner_hardware = MinerHardware::from(&self.model);
+
+        let api_version = data
+            .extract::<String>(DataField::ApiVersion)
+            .map(|v| FirmwareVersion::parse_api(&v));
+        let firmware_version = data
+            .extract::<String>(DataField::FirmwareVersion)
+            .map(|v| FirmwareVersion::parse(MinerFirmware::BraiinsOS, &v));
+        let uptime = data.extract_map::<u64, _>(DataField::Uptime, Duration::from_secs);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get system time")
+            .as_secs();
+
+        let hashrate = data.extract_map::<f64, _>(DataField::Hashrate, |f| HashRate {
+            value: f,
+            unit: HashRateUnit::MegaHash,
+            algo: String::from("SHA256"),
+        });
+
+        // the board details are split across several commands, which are matched up by
+        // their order as there is only a single board
+        let devs = collector.response(DEVS_CMD).map(|d| &d["DEVS"]);
+        let temps = collector.response(TEMPS_CMD).map(|t| &t["TEMPS"]);
+        let tuner = collector
+            .response(TUNERSTATUS_CMD)
+            .map(|t| &t["TUNERSTATUS"][0]["TunerChainStatus"]);
+
+        let hashboards: Vec<BoardData> = data
+            .get(&DataField::Hashboards)
+            .and_then(|v| v.as_array())
+            .map(|boards| {
+                boards
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, board)| {
+                        parse_board(
+                            idx,
+                            board,
+                            devs.map(|d| &d[idx]),
+                            temps.map(|t| &t[idx]),
+                            tuner.map(|t| &t[idx]),
+                            miner_hardware.chips,
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let fans = data
+            .get(&DataField::Fans)
+            .and_then(|v| v.as_array())
+            .map(|fans| {
+                fans.iter()
+                    .enumerate()
+                    .filter_map(|(idx, fan)| {
+                        Some(FanData {
+                            position: fan["ID"].as_i64().unwrap_or(idx as i64) as i16,
+                            rpm: AngularVelocity::from_rpm(fan["RPM"].as_f64()?),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // temps reports the average chip temperature of each board, which is closer to
+        // what other backends report than the board temperature
+        let average_temperature = {
+            let temps: Vec<f64> = temps
+                .and_then(|t| t.as_array())
+                .map(|t| t.iter().filter_map(|b| b["Chip"].as_f64()).collect())
+                .unwrap_or_default();
+            if temps.is_empty() {
+                None
+            } else {
+                Some(Temperature::from_celsius(
+                    temps.iter().sum::<f64>() / temps.len() as f64,
+                ))
+            }
+        };
+
+        let wattage = data.extract_map::<f64, _>(DataField::Wattage, Power::from_watts);
+        let wattage_limit = data.extract_map::<f64, _>(DataField::WattageLimit, Power::from_watts);
+        let efficiency = match (&hashrate, wattage) {
+            (Some(hr), Some(w)) if hr.value > 0.0 => Some(w / hr.as_unit(HashRateUnit::TeraHash)),
+            _ => None,
+        };
+
+        let pools = data
+            .get(&DataField::Pools)
+            .and_then(|v| v.as_array())
+            .map(|pools| pools.iter().enumerate().map(parse_pool).collect())
+            .unwrap_or_default();
+
+        let total_chips = hashboards
+            .iter()
+            .map(|b| b.working_chips)
+            .sum::<Option<u16>>();
+        let expected_chips = match (miner_hardware.chips, miner_hardware.boards) {
+            (Some(chips), Some(boards)) => Some(chips * boards as u16),
+            _ => None,
+        };
+        let is_mining = hashrate.as_ref().is_some_and(|hr| hr.value > 0.0);
+
+        MinerData {
+            schema_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp,
+            ip: self.ip,
+            target_name: self.target.hostname().map(String::from),
+            mac: None,
+            device_info: DeviceInfo::new(
+                Braiins,
+                self.model.clone(),
+                MinerFirmware::BraiinsOS,
+                HashAlgorithm::SHA256,
+            ),
+            serial_number: None,
+            hostname: None,
+            api_version,
+            firmware_version,
+            control_board_version: None,
+            expected_hashboards: miner_hardware.boards,
+            hashboards,
+            hashrate,
+            expected_chips,
+            total_chips,
+            expected_fans: miner_hardware.fans,
+            fans,
+            psu_fans: vec![],
+            average_temperature,
+            fluid_temperature: None,
+            wattage,
+            wattage_limit,
+            efficiency,
+            light_flashing: None,
+            messages: vec![],
+            uptime,
+            is_mining,
+            pools,
+            location: None,
+        }
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        const SUMMARY_CMD: &str = "summary";
+        const DEVDETAILS_CMD: &str = "devdetails";
+        const FANS_CMD: &str = "fans";
+        const POOLS_CMD: &str = "pools";
+        const VERSION_CMD: &str = "version";
+
+        match data_field {
+            DataField::ApiVersion => &[(
+                VERSION_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/VERSION/0/API"),
+                },
+            )],
+            DataField::FirmwareVersion => &[(
+                VERSION_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/VERSION/0/BOSminer"),
+                },
+            )],
+            DataField::Hashrate => &[
+                (
+                    SUMMARY_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/SUMMARY/0/MHS 5s"),
+                    },
+                ),
+                (
+                    SUMMARY_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/SUMMARY/0/MHS av"),
+                    },
+                ),
+            ],
+            DataField::Uptime => &[(
+                SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/SUMMARY/0/Elapsed"),
+                },
+            )],
+            // devdetails lists the boards, the rest are read from the collector's responses
+            DataField::Hashboards => &[
+                (
+                    DEVDETAILS_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/DEVDETAILS"),
+                    },
+                ),
+                (
+                    DEVS_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/DEVS"),
+                    },
+                ),
+                (
+                    TEMPS_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/TEMPS"),
+                    },
+                ),
+                (
+                    TUNERSTATUS_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/TUNERSTATUS/0/TunerChainStatus"),
+                    },
+                ),
+            ],
+            DataField::Wattage => &[(
+                TUNERSTATUS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/TUNERSTATUS/0/ApproximateMinerPowerConsumption"),
+                },
+            )],
+            DataField::WattageLimit => &[(
+                TUNERSTATUS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/TUNERSTATUS/0/PowerLimit"),
+                },
+            )],
+            DataField::Fans => &[(
+                FANS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/FANS"),
+                },
+            )],
+            DataField::Pools => &[(
+                POOLS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/POOLS"),
+                },
+            )],
+            _ => &[],
+        }
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::RPC { command } => {
+                SendRPCCommand::send_command(&self.rpc, command, params)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
+    }
+}
+
+fn parse_board(
+    idx: usize,
+    details: &Value,
+    dev: Option<&Value>,
+    temps: Option<&Value>,
+    tuner: Option<&Value>,
+    expected_chips: Option<u16>,
+) -> BoardData {
+    let hashrate = dev.and_then(|d| d["MHS 5s"].as_f64()).map(|f| HashRate {
+        value: f,
+        unit: HashRateUnit::MegaHash,
+        algo: String::from("SHA256"),
+    });
+    let expected_hashrate = dev
+        .and_then(|d| d["Nominal MHS"].as_f64())
+        .map(|f| HashRate {
+            value: f,
+            unit: HashRateUnit::MegaHash,
+            algo: String::from("SHA256"),
+        });
+    let working_chips = details["Chips"].as_u64().map(|u| u as u16);
+
+    BoardData {
+        position: idx as u8,
+        active: Some(hashrate.as_ref().is_some_and(|hr| hr.value > 0.0)),
+        hashrate,
+        expected_hashrate,
+        board_temperature: temps
+            .and_then(|t| t["Board"].as_f64())
+            .map(Temperature::from_celsius),
+        intake_temperature: None,
+        outlet_temperature: None,
+        // the hardware table does not know the chip count, so trust the detected count
+        expected_chips: expected_chips.or(working_chips),
+        working_chips,
+        serial_number: None,
+        chips: vec![],
+        voltage: details["Voltage"].as_f64().map(Voltage::from_volts),
+        frequency: details["Frequency"].as_f64().map(Frequency::from_megahertz),
+        tuned: tuner
+            .and_then(|t| t["Status"].as_str())
+            .map(|status| status == "Stable"),
+    }
+}
+
+fn parse_pool((idx, pool): (usize, &Value)) -> PoolData {
+    PoolData {
+        position: Some(pool["POOL"].as_u64().unwrap_or(idx as u64) as u16),
+        url: pool["URL"].as_str().and_then(PoolURL::parse),
+        accepted_shares: pool["Accepted"].as_u64(),
+        rejected_shares: pool["Rejected"].as_u64(),
+        active: pool["Stratum Active"].as_bool(),
+        alive: pool["Status"].as_str().map(|s| s == "Alive"),
+        user: pool["User"].as_str().map(String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_board() {
+        // responses in the shape Braiins OS returns on a BMM100
+        let devdetails = json!({
+            "DEVDETAILS": [{
+                "DEVDETAILS": 0, "Name": "Hashchain", "ID": 0, "Driver": "bosminer",
+                "Kernel": "", "Model": "BMM100", "Device Path": "", "Chips": 3,
+                "Cores": 1608, "Voltage": 12.0, "Frequency": 525.0
+            }]
+        });
+        let devs = json!({
+            "DEVS": [{
+                "ASC": 0, "Name": "Hashchain", "ID": 0, "Enabled": "Y", "Status": "Alive",
+                "MHS av": 1012345.67, "MHS 5s": 1023456.78, "Nominal MHS": 1000000.0,
+                "Hardware Errors": 0
+            }]
+        });
+        let temps = json!({
+            "TEMPS": [{"TEMPS": 0, "ID": 0, "Board": 44.0, "Chip": 61.5}]
+        });
+        let tuner = json!({"HashchainIndex": 0, "Status": "Stable"});
+
+        let board = parse_board(
+            0,
+            &devdetails["DEVDETAILS"][0],
+            Some(&devs["DEVS"][0]),
+            Some(&temps["TEMPS"][0]),
+            Some(&tuner),
+            None,
+        );
+
+        assert_eq!(board.active, Some(true));
+        assert_eq!(board.hashrate.map(|hr| hr.value), Some(1023456.78));
+        assert_eq!(board.expected_hashrate.map(|hr| hr.value), Some(1000000.0));
+        assert_eq!(
+            board.board_temperature,
+            Some(Temperature::from_celsius(44.0))
+        );
+        assert_eq!(board.outlet_temperature, None);
+        assert_eq!(board.working_chips, Some(3));
+        assert_eq!(board.expected_chips, Some(3));
+        assert_eq!(board.frequency, Some(Frequency::from_megahertz(525.0)));
+        assert_eq!(board.tuned, Some(true));
+    }
+}
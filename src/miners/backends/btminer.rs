@@ -1,13 +1,17 @@
-use super::traits::GetMinerData;
+use super::traits::{GetMinerData, RawCommandError};
 use crate::data::board::BoardData;
 use crate::data::device::{DeviceInfo, HashAlgorithm, MinerFirmware, MinerMake, MinerModel};
 use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
 use crate::data::hashrate::{HashRate, HashRateUnit};
 use crate::data::miner::MinerData;
 use crate::data::pool::{PoolData, PoolURL};
 use crate::miners::api::rpc::errors::RPCError;
 use crate::miners::api::rpc::{btminer::BTMinerV3RPC, traits::SendRPCCommand};
-use crate::miners::data::{DataField, DataLocation};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{DataExtractor, DataField, DataLocation, get_by_pointer};
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
 use async_trait::async_trait;
 use macaddr::MacAddr;
 use measurements::{AngularVelocity, Frequency, Power, Temperature, Voltage};
@@ -17,25 +21,41 @@ use std::net::IpAddr;
 use std::str::FromStr;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+// the status commands differ by their parameter, which is included to tell them apart
+const DEVICE_INFO_CMD: &str = "get.device.info";
+const STATUS_SUMMARY_CMD: &str = "get.miner.status:summary";
+const STATUS_POOLS_CMD: &str = "get.miner.status:pools";
+const STATUS_EDEVS_CMD: &str = "get.miner.status:edevs";
+
 pub struct BTMinerV3Backend {
     pub ip: IpAddr,
     pub rpc: BTMinerV3RPC,
     pub device_info: DeviceInfo,
+    target: MinerTarget,
 }
 
 impl BTMinerV3Backend {
-    pub fn new(ip: IpAddr, model: MinerModel) -> Self {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(target: impl Into<MinerTarget>, model: MinerModel) -> Self {
+        let target = target.into();
         BTMinerV3Backend {
-            ip,
-            rpc: BTMinerV3RPC::new(ip, None),
+            ip: target.data_ip(),
+            rpc: BTMinerV3RPC::new(target.host(), None),
             device_info: DeviceInfo::new(
                 MinerMake::WhatsMiner,
                 model,
                 MinerFirmware::Stock,
                 HashAlgorithm::SHA256,
             ),
+            target,
         }
     }
+    /// Use a non-standard port for the v3 API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.rpc = BTMinerV3RPC::new(self.target.host(), Some(ports.btminer_port()));
+        self
+    }
+
     pub async fn get_device_info(&self) -> Result<GetDeviceInfo, RPCError> {
         self.rpc
             .send_command::<GetDeviceInfo, ()>("get.device.info", None)
@@ -60,52 +80,82 @@ impl BTMinerV3Backend {
 
 #[async_trait]
 impl GetMinerData for BTMinerV3Backend {
-    async fn get_data(&self) -> MinerData {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let needs = |command: &str| {
+            fields
+                .iter()
+                .flat_map(|&field| self.get_locations(field))
+                .any(|(cmd, _)| *cmd == command)
+        };
         let (device_info, miner_status_summary, miner_status_pools, miner_status_edevs) = tokio::join!(
-            self.get_device_info(),
-            self.get_miner_status_summary(),
-            self.get_miner_status_pools(),
-            self.get_miner_status_edevs(),
+            async {
+                match needs(DEVICE_INFO_CMD) {
+                    true => self.get_device_info().await.ok(),
+                    false => None,
+                }
+            },
+            async {
+                match needs(STATUS_SUMMARY_CMD) {
+                    true => self.get_miner_status_summary().await.ok(),
+                    false => None,
+                }
+            },
+            async {
+                match needs(STATUS_POOLS_CMD) {
+                    true => self.get_miner_status_pools().await.ok(),
+                    false => None,
+                }
+            },
+            async {
+                match needs(STATUS_EDEVS_CMD) {
+                    true => self.get_miner_status_edevs().await.ok(),
+                    false => None,
+                }
+            },
         );
 
         // construct hashboard info
+        let board_count = match miner_status_edevs {
+            Some(_) => self.device_info.hardware.boards.unwrap_or(0),
+            None => 0,
+        };
         let mut boards: Vec<BoardData> = Vec::new();
-        for position in 0..self.device_info.hardware.boards.unwrap_or(0) {
+        for position in 0..board_count {
             let hashrate = match &miner_status_edevs {
-                Ok(edevs) => edevs.board_hashrates.get(position as usize).cloned(),
+                Some(edevs) => edevs.board_hashrates.get(position as usize).cloned(),
                 _ => None,
             };
             let expected_hashrate = match &miner_status_edevs {
-                Ok(edevs) => edevs
+                Some(edevs) => edevs
                     .board_expected_hashrates
                     .get(position as usize)
                     .cloned(),
                 _ => None,
             };
             let outlet_temperature = match &miner_status_edevs {
-                Ok(edevs) => edevs
+                Some(edevs) => edevs
                     .board_outlet_temperatures
                     .get(position as usize)
                     .cloned(),
                 _ => None,
             };
             let intake_temperature = match &miner_status_edevs {
-                Ok(edevs) => edevs
+                Some(edevs) => edevs
                     .board_intake_temperatures
                     .get(position as usize)
                     .cloned(),
                 _ => None,
             };
             let working_chips = match &miner_status_edevs {
-                Ok(edevs) => edevs.board_working_chips.get(position as usize).cloned(),
+                Some(edevs) => edevs.board_working_chips.get(position as usize).cloned(),
                 _ => None,
             };
             let frequency = match &miner_status_edevs {
-                Ok(edevs) => edevs.board_freqs.get(position as usize).cloned(),
+                Some(edevs) => edevs.board_freqs.get(position as usize).cloned(),
                 _ => None,
             };
             let serial_number = match &device_info {
-                Ok(info) => info.board_sns.get(position as usize).cloned(),
+                Some(info) => info.board_sns.get(position as usize).cloned(),
                 _ => None,
             };
 
@@ -121,7 +171,7 @@ impl GetMinerData for BTMinerV3Backend {
                 serial_number,
                 chips: Vec::new(),
                 voltage: match &device_info {
-                    Ok(info) => info.voltage,
+                    Some(info) => info.voltage,
                     _ => None,
                 },
                 frequency,
@@ -135,11 +185,11 @@ impl GetMinerData for BTMinerV3Backend {
             .as_secs();
 
         let hashrate = match &miner_status_summary {
-            Ok(summary) => summary.hashrate.clone(),
+            Some(summary) => summary.hashrate.clone(),
             _ => None,
         };
         let wattage = match &miner_status_summary {
-            Ok(summary) => summary.wattage.clone(),
+            Some(summary) => summary.wattage.clone(),
             _ => None,
         };
         let efficiency = match (hashrate.clone(), wattage.clone()) {
@@ -151,29 +201,33 @@ impl GetMinerData for BTMinerV3Backend {
             schema_version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp,
             ip: self.ip.clone(),
+            target_name: self.target.hostname().map(String::from),
             mac: match &device_info {
-                Ok(info) => info.mac.clone(),
+                Some(info) => info.mac.clone(),
                 _ => None,
             },
             device_info: self.device_info.clone(),
             serial_number: match &device_info {
-                Ok(info) => info.serial_number.clone(),
+                Some(info) => info.serial_number.clone(),
                 _ => None,
             },
             hostname: match &device_info {
-                Ok(info) => info.hostname.clone(),
+                Some(info) => info.hostname.clone(),
                 _ => None,
             },
             api_version: match &device_info {
-                Ok(info) => info.api_version.clone(),
+                Some(info) => info.api_version.as_deref().map(FirmwareVersion::parse_api),
                 _ => None,
             },
             firmware_version: match &device_info {
-                Ok(info) => info.fw_version.clone(),
+                Some(info) => info
+                    .fw_version
+                    .as_deref()
+                    .map(|v| FirmwareVersion::parse(MinerFirmware::Stock, v)),
                 _ => None,
             },
             control_board_version: match &device_info {
-                Ok(info) => info.control_board_version.clone(),
+                Some(info) => info.control_board_version.clone(),
                 _ => None,
             },
             expected_hashboards: self.device_info.hardware.boards.clone(),
@@ -187,14 +241,16 @@ impl GetMinerData for BTMinerV3Backend {
                 (Some(chips), _) => Some(chips.clone()),
                 _ => None,
             },
-            total_chips: Some(boards.iter().filter_map(|b| b.working_chips).sum()),
+            total_chips: miner_status_edevs
+                .as_ref()
+                .map(|_| boards.iter().filter_map(|b| b.working_chips).sum()),
             expected_fans: self.device_info.hardware.fans.clone(),
             fans: match &miner_status_summary {
-                Ok(summary) => summary.fans.clone(),
+                Some(summary) => summary.fans.clone(),
                 _ => Vec::new(),
             },
             psu_fans: match &device_info {
-                Ok(info) => info.psu_fans.clone(),
+                Some(info) => info.psu_fans.clone(),
                 _ => Vec::new(),
             },
             average_temperature: {
@@ -212,34 +268,194 @@ impl GetMinerData for BTMinerV3Backend {
                 }
             },
             fluid_temperature: match &miner_status_summary {
-                Ok(summary) => summary.fluid_temperature.clone(),
+                Some(summary) => summary.fluid_temperature.clone(),
                 _ => None,
             },
             wattage,
             wattage_limit: match &device_info {
-                Ok(info) => info.wattage_limit.clone(),
+                Some(info) => info.wattage_limit.clone(),
                 _ => None,
             },
             efficiency,
             light_flashing: match &device_info {
-                Ok(info) => info.light_flashing.clone(),
+                Some(info) => info.light_flashing.clone(),
                 _ => None,
             },
             messages: Vec::new(),
             uptime: match &miner_status_summary {
-                Ok(summary) => summary.uptime.clone(),
+                Some(summary) => summary.uptime.clone(),
                 _ => None,
             },
             is_mining: true,
             pools: match &miner_status_pools {
-                Ok(pools) => pools.pools.clone(),
+                Some(pools) => pools.pools.clone(),
                 _ => Vec::new(),
             },
+            location: None,
+        }
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        match data_field {
+            DataField::Mac => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/network/mac"),
+                },
+            )],
+            DataField::SerialNumber => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/miner/miner-sn"),
+                },
+            )],
+            DataField::Hostname => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/network/hostname"),
+                },
+            )],
+            DataField::ApiVersion => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/system/api"),
+                },
+            )],
+            DataField::FirmwareVersion => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/system/fwversion"),
+                },
+            )],
+            DataField::ControlBoardVersion => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/system/platform"),
+                },
+            )],
+            DataField::Hashboards => &[
+                (
+                    STATUS_EDEVS_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/msg/edevs"),
+                    },
+                ),
+                (
+                    DEVICE_INFO_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/msg/miner"),
+                    },
+                ),
+            ],
+            DataField::Hashrate => &[(
+                STATUS_SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/summary/hash-realtime"),
+                },
+            )],
+            DataField::TotalChips => &[(
+                STATUS_EDEVS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/edevs"),
+                },
+            )],
+            DataField::Fans => &[(
+                STATUS_SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/summary"),
+                },
+            )],
+            DataField::PsuFans => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/power/fanspeed"),
+                },
+            )],
+            DataField::AverageTemperature => &[(
+                STATUS_EDEVS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/edevs"),
+                },
+            )],
+            DataField::FluidTemperature => &[(
+                STATUS_SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/summary/environment-temperature"),
+                },
+            )],
+            DataField::Wattage => &[(
+                STATUS_SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/summary/power-realtime"),
+                },
+            )],
+            DataField::WattageLimit => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/miner/power-limit-set"),
+                },
+            )],
+            DataField::Efficiency => &[(
+                STATUS_SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/summary"),
+                },
+            )],
+            DataField::LightFlashing => &[(
+                DEVICE_INFO_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/system/ledstatus"),
+                },
+            )],
+            DataField::Uptime => &[(
+                STATUS_SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/summary/elapsed"),
+                },
+            )],
+            DataField::Pools => &[(
+                STATUS_POOLS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/msg/pools"),
+                },
+            )],
+            _ => &[],
         }
     }
 
-    fn get_locations(&self, _data_field: DataField) -> &'static [DataLocation] {
-        todo!()
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::RPC { command } => {
+                SendRPCCommand::send_command(&self.rpc, command, params)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
     }
 }
 
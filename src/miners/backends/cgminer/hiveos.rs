@@ -0,0 +1,106 @@
+use measurements::Power;
+use serde_json::Value;
+
+use super::{CGMinerAdapter, apply_antminer_stats, parse_number, total_board_hashrate};
+use crate::data::device::{MinerFirmware, MinerMake};
+use crate::data::hashrate::HashRateUnit;
+use crate::data::miner::MinerData;
+
+/// Adapter for AntMiners running Hiveon ASIC firmware, which adds autotuning and power
+/// readings to the AntMiner `stats` fields
+pub struct HiveOSAdapter;
+
+impl CGMinerAdapter for HiveOSAdapter {
+    fn make(&self) -> MinerMake {
+        MinerMake::AntMiner
+    }
+
+    fn firmware(&self) -> MinerFirmware {
+        MinerFirmware::HiveOS
+    }
+
+    fn apply_stats(&self, stats: &Value, data: &mut MinerData) {
+        apply_antminer_stats(stats, data);
+
+        let Some(stats) = stats["STATS"].as_array().and_then(|s| s.last()) else {
+            return;
+        };
+
+        // the autotune profile applies to every chain, while the per chain status
+        // says whether that chain has finished tuning to it
+        let profile_active = stats["autotune_profile"]
+            .as_str()
+            .map(|p| !p.is_empty() && !p.eq_ignore_ascii_case("off"));
+        let mut chain_power = vec![];
+        for board in data.hashboards.iter_mut() {
+            let chain = board.position + 1;
+            board.tuned = match stats[format!("chain_tune_status{}", chain)].as_str() {
+                Some(status) => Some(matches!(status, "done" | "tuned" | "finished")),
+                None => profile_active,
+            };
+            if let Some(power) = parse_number(&stats[format!("chain_power{}", chain)]) {
+                chain_power.push(power);
+            }
+        }
+
+        data.wattage = parse_number(&stats["total_power"])
+            .or_else(|| (!chain_power.is_empty()).then(|| chain_power.iter().sum()))
+            .map(Power::from_watts);
+        data.wattage_limit = parse_number(&stats["power_limit"]).map(Power::from_watts);
+        // `summary` is the usual source, fall back to the `chain_rate` readings without it
+        if data.hashrate.is_none() {
+            data.hashrate = total_board_hashrate(&data.hashboards);
+        }
+        data.efficiency = match (&data.hashrate, data.wattage) {
+            (Some(hr), Some(w)) if hr.value > 0.0 => Some(w / hr.as_unit(HashRateUnit::TeraHash)),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::net::IpAddr;
+
+    #[test]
+    fn test_apply_stats() {
+        let mut data = MinerData::empty(IpAddr::from([10, 0, 0, 1]));
+        let stats = json!({"STATS": [
+            {"BMMiner": "1.0.0", "Miner": "Hiveon 1.02@210419"},
+            {"autotune_profile": "1350W", "power_limit": 1500,
+             "chain_acn1": 63, "chain_rate1": "4700.10", "chain_power1": 440,
+             "chain_tune_status1": "done",
+             "chain_acn2": 63, "chain_rate2": "4680.35", "chain_power2": "445",
+             "chain_tune_status2": "tuning",
+             "chain_acn3": 63, "chain_rate3": "4695.00", "chain_power3": 450}
+        ]});
+
+        HiveOSAdapter.apply_stats(&stats, &mut data);
+
+        let tuned: Vec<_> = data.hashboards.iter().map(|b| b.tuned).collect();
+        // the third chain has no status, so the active profile is used
+        assert_eq!(tuned, vec![Some(true), Some(false), Some(true)]);
+        assert_eq!(data.wattage, Some(Power::from_watts(1335.0)));
+        assert_eq!(data.wattage_limit, Some(Power::from_watts(1500.0)));
+        let hashrate = data
+            .hashrate
+            .as_ref()
+            .expect("chain rates should be summed");
+        assert!((hashrate.as_unit(HashRateUnit::GigaHash).value - 14075.45).abs() < 1e-9);
+        assert!(data.efficiency.is_some());
+
+        let mut data = MinerData::empty(IpAddr::from([10, 0, 0, 1]));
+        let stats = json!({"STATS": [
+            {"BMMiner": "1.0.0"},
+            {"autotune_profile": "off", "total_power": 3100, "chain_acn1": 76}
+        ]});
+
+        HiveOSAdapter.apply_stats(&stats, &mut data);
+
+        assert_eq!(data.hashboards[0].tuned, Some(false));
+        assert_eq!(data.wattage, Some(Power::from_watts(3100.0)));
+        assert_eq!(data.efficiency, None);
+    }
+}
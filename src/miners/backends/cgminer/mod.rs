@@ -0,0 +1,589 @@
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use measurements::{AngularVelocity, Temperature};
+use serde_json::Value;
+
+use crate::data::board::BoardData;
+use crate::data::device::{
+    DeviceInfo, HashAlgorithm, MinerFirmware, MinerHardware, MinerMake, MinerModel,
+};
+use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
+use crate::data::hashrate::{HashRate, HashRateUnit};
+use crate::data::miner::MinerData;
+use crate::data::pool::{PoolData, PoolURL};
+use crate::miners::api::rpc::cgminer::CGMinerRPC;
+use crate::miners::api::rpc::traits::SendRPCCommand;
+use crate::miners::backends::traits::{GetMinerData, RawCommandError};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{
+    DataCollector, DataExtensions, DataExtractor, DataField, DataLocation, get_by_pointer,
+};
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
+
+pub mod hiveos;
+pub mod mskminer;
+pub mod stock;
+
+pub use hiveos::HiveOSAdapter;
+pub use mskminer::MSKMinerAdapter;
+pub use stock::StockCGMinerAdapter;
+
+const SUMMARY_CMD: &str = "summary";
+const DEVS_CMD: &str = "devs";
+const POOLS_CMD: &str = "pools";
+const VERSION_CMD: &str = "version";
+/// Vendor specific fields for the adapters, only sent when a field located in it is requested
+const STATS_CMD: &str = "stats";
+
+/// Vendor specific extensions to the generic [`CGMiner`] backend.
+///
+/// The standard `summary`, `pools`, `devs` and `version` output is handled by the backend,
+/// an adapter only needs to describe the make and firmware and map its own `stats` fields.
+pub trait CGMinerAdapter: Send + Sync {
+    /// The make this adapter is for
+    fn make(&self) -> MinerMake;
+
+    /// The firmware this adapter is for
+    fn firmware(&self) -> MinerFirmware {
+        MinerFirmware::Stock
+    }
+
+    /// The hashing algorithm mined by this make
+    fn algo(&self) -> HashAlgorithm {
+        HashAlgorithm::SHA256
+    }
+
+    /// Fill in vendor specific data from the `stats` command response
+    fn apply_stats(&self, _stats: &Value, _data: &mut MinerData) {}
+}
+
+/// Generic backend for miners exposing a CGMiner compatible RPC API on port 4028
+pub struct CGMiner {
+    model: MinerModel,
+    rpc: CGMinerRPC,
+    adapter: Box<dyn CGMinerAdapter>,
+    ip: IpAddr,
+    target: MinerTarget,
+}
+
+impl CGMiner {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(
+        target: impl Into<MinerTarget>,
+        model: MinerModel,
+        adapter: Box<dyn CGMinerAdapter>,
+    ) -> Self {
+        let target = target.into();
+        CGMiner {
+            model,
+            rpc: CGMinerRPC::new(target.host(), None),
+            adapter,
+            ip: target.data_ip(),
+            target,
+        }
+    }
+
+    /// Use a non-standard port for the RPC API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.rpc = CGMinerRPC::new(self.target.host(), Some(ports.rpc_port()));
+        self
+    }
+
+    /// Get the adapter for a make running stock firmware
+    pub fn adapter_for(make: MinerMake) -> Box<dyn CGMinerAdapter> {
+        Box::new(StockCGMinerAdapter(make))
+    }
+
+    /// Get the adapter for a third party firmware, if it has one
+    pub fn adapter_for_firmware(firmware: MinerFirmware) -> Option<Box<dyn CGMinerAdapter>> {
+        match firmware {
+            MinerFirmware::HiveOS => Some(Box::new(HiveOSAdapter)),
+            MinerFirmware::MSKMiner => Some(Box::new(MSKMinerAdapter)),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl GetMinerData for CGMiner {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut collector = DataCollector::new(self, &self.rpc);
+        collector.fetch(fields).await;
+        let data = collector.extract(fields);
+
+        let algo = self.adapter.algo();
+        let miner_hardware = MinerHardware::from(&self.model);
+
+        let api_version = data
+            .extract::<String>(DataField::ApiVersion)
+            .map(|v| FirmwareVersion::parse_api(&v));
+        let firmware_version = data
+            .extract::<String>(DataField::FirmwareVersion)
+            .map(|v| FirmwareVersion::parse(self.adapter.firmware(), &v));
+        let uptime = data.extract_map::<u64, _>(DataField::Uptime, Duration::from_secs);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get system time")
+            .as_secs();
+
+        let hashrate = data
+            .get(&DataField::Hashrate)
+            .and_then(|summary| parse_hashrate(summary, &algo));
+
+        let hashboards: Vec<BoardData> = collector
+            .response(DEVS_CMD)
+            .and_then(|devs| devs["DEVS"].as_array())
+            .map(|boards| {
+                boards
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, board)| parse_dev(idx, board, &algo, miner_hardware.chips))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let pools = data
+            .get(&DataField::Pools)
+            .and_then(|v| v.as_array())
+            .map(|pools| pools.iter().enumerate().map(parse_pool).collect())
+            .unwrap_or_default();
+
+        let is_mining = hashrate.as_ref().is_some_and(|hr| hr.value > 0.0);
+
+        let mut miner_data = MinerData {
+            schema_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp,
+            ip: self.ip,
+            target_name: self.target.hostname().map(String::from),
+            mac: None,
+            device_info: DeviceInfo::new(
+                self.adapter.make(),
+                self.model.clone(),
+                self.adapter.firmware(),
+                algo,
+            ),
+            serial_number: None,
+            hostname: None,
+            api_version,
+            firmware_version,
+            control_board_version: None,
+            expected_hashboards: miner_hardware.boards,
+            hashboards,
+            hashrate,
+            expected_chips: match (miner_hardware.chips, miner_hardware.boards) {
+                (Some(chips), Some(boards)) => Some(chips * boards as u16),
+                _ => None,
+            },
+            total_chips: None,
+            expected_fans: miner_hardware.fans,
+            fans: vec![],
+            psu_fans: vec![],
+            average_temperature: None,
+            fluid_temperature: None,
+            wattage: None,
+            wattage_limit: None,
+            efficiency: None,
+            light_flashing: None,
+            messages: vec![],
+            uptime,
+            is_mining,
+            pools,
+            location: None,
+        };
+
+        // vendor fields can add boards, chips and temperatures, so totals are computed after
+        if let Some(stats) = collector.response(STATS_CMD) {
+            self.adapter.apply_stats(stats, &mut miner_data);
+        }
+        if fields.contains(&DataField::TotalChips) {
+            miner_data.total_chips = miner_data
+                .hashboards
+                .iter()
+                .map(|b| b.working_chips)
+                .sum::<Option<u16>>();
+        }
+        if fields.contains(&DataField::AverageTemperature) {
+            miner_data.average_temperature = average_temperature(&miner_data.hashboards);
+        }
+
+        miner_data
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        match data_field {
+            DataField::ApiVersion => &[(
+                VERSION_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/VERSION/0/API"),
+                },
+            )],
+            DataField::FirmwareVersion => &[(
+                VERSION_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/VERSION/0/CGMiner"),
+                },
+            )],
+            DataField::Hashrate => &[(
+                SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/SUMMARY/0"),
+                },
+            )],
+            DataField::Uptime => &[(
+                SUMMARY_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/SUMMARY/0/Elapsed"),
+                },
+            )],
+            DataField::Hashboards => &[
+                (
+                    DEVS_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/DEVS"),
+                    },
+                ),
+                (
+                    STATS_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some("/STATS"),
+                    },
+                ),
+            ],
+            DataField::Fans | DataField::Wattage | DataField::WattageLimit => &[(
+                STATS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/STATS"),
+                },
+            )],
+            DataField::Pools => &[(
+                POOLS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/POOLS"),
+                },
+            )],
+            _ => &[],
+        }
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::RPC { command } => {
+                SendRPCCommand::send_command(&self.rpc, command, params)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
+    }
+}
+
+fn average_temperature(hashboards: &[BoardData]) -> Option<Temperature> {
+    let temps: Vec<f64> = hashboards
+        .iter()
+        .filter_map(|b| b.outlet_temperature.or(b.board_temperature))
+        .map(|t| t.as_celsius())
+        .collect();
+    if temps.is_empty() {
+        None
+    } else {
+        Some(Temperature::from_celsius(
+            temps.iter().sum::<f64>() / temps.len() as f64,
+        ))
+    }
+}
+
+fn parse_dev(
+    idx: usize,
+    dev: &Value,
+    algo: &HashAlgorithm,
+    expected_chips: Option<u16>,
+) -> BoardData {
+    let hashrate = parse_hashrate(dev, algo);
+    let position = dev["ASC"]
+        .as_u64()
+        .or(dev["ID"].as_u64())
+        .unwrap_or(idx as u64) as u8;
+
+    BoardData {
+        position,
+        active: dev["Enabled"].as_str().map(|e| e == "Y"),
+        hashrate,
+        expected_hashrate: None,
+        board_temperature: dev["Temperature"]
+            .as_f64()
+            .filter(|t| *t > 0.0)
+            .map(Temperature::from_celsius),
+        intake_temperature: None,
+        outlet_temperature: None,
+        expected_chips,
+        working_chips: None,
+        serial_number: None,
+        chips: vec![],
+        voltage: None,
+        frequency: None,
+        tuned: None,
+    }
+}
+
+/// Parse the hashrate of a `summary` or `devs` entry, which CGMiner reports in MH/s and
+/// AntMiner style firmware in GH/s, often as a string
+fn parse_hashrate(entry: &Value, algo: &HashAlgorithm) -> Option<HashRate> {
+    [
+        ("MHS 5s", HashRateUnit::MegaHash),
+        ("MHS av", HashRateUnit::MegaHash),
+        ("GHS 5s", HashRateUnit::GigaHash),
+        ("GHS av", HashRateUnit::GigaHash),
+    ]
+    .into_iter()
+    .find_map(|(key, unit)| {
+        parse_number(&entry[key]).map(|value| HashRate {
+            value,
+            unit,
+            algo: algo.to_string(),
+        })
+    })
+}
+
+fn parse_pool((idx, pool): (usize, &Value)) -> PoolData {
+    PoolData {
+        position: Some(pool["POOL"].as_u64().unwrap_or(idx as u64) as u16),
+        url: pool["URL"].as_str().and_then(PoolURL::parse),
+        accepted_shares: pool["Accepted"].as_u64(),
+        rejected_shares: pool["Rejected"].as_u64(),
+        active: pool["Stratum Active"].as_bool(),
+        alive: pool["Status"].as_str().map(|s| s == "Alive"),
+        user: pool["User"].as_str().map(String::from),
+    }
+}
+
+/// Apply the AntMiner style `stats` fields most CGMiner clones copy, such as `fan1`,
+/// `chain_acn1`, `chain_rate1` and `temp_pcb1`, to the fans and hashboards of a set of miner data.
+pub(crate) fn apply_antminer_stats(stats: &Value, data: &mut MinerData) {
+    // the first entry only holds version info, the miner details are in the last one
+    let Some(stats) = stats["STATS"].as_array().and_then(|s| s.last()) else {
+        return;
+    };
+
+    let fans: Vec<FanData> = (1..=8)
+        .filter_map(|idx| {
+            let rpm = stats[format!("fan{}", idx)].as_f64()?;
+            Some(FanData {
+                position: idx as i16 - 1,
+                rpm: AngularVelocity::from_rpm(rpm),
+            })
+        })
+        .collect();
+    if !fans.is_empty() {
+        data.fans = fans;
+    }
+
+    for chain in 1..=16u8 {
+        let working_chips = stats[format!("chain_acn{}", chain)].as_u64();
+        let rate = parse_number(&stats[format!("chain_rate{}", chain)]);
+        // `temp_chip` readings are per sensor rather than per chip, and sit on the chips
+        // rather than at the board intake or outlet, so only the PCB readings are used
+        let pcb_temps = parse_temps(&stats[format!("temp_pcb{}", chain)]);
+        if working_chips.is_none() && rate.is_none() && pcb_temps.is_empty() {
+            continue;
+        }
+
+        let position = chain - 1;
+        let board = match data
+            .hashboards
+            .iter_mut()
+            .position(|b| b.position == position)
+        {
+            Some(idx) => &mut data.hashboards[idx],
+            None => {
+                data.hashboards.push(BoardData {
+                    position,
+                    hashrate: None,
+                    expected_hashrate: None,
+                    board_temperature: None,
+                    intake_temperature: None,
+                    outlet_temperature: None,
+                    expected_chips: data.device_info.hardware.chips,
+                    working_chips: None,
+                    serial_number: None,
+                    chips: vec![],
+                    voltage: None,
+                    frequency: None,
+                    tuned: None,
+                    active: None,
+                });
+                data.hashboards.last_mut().expect("board was just added")
+            }
+        };
+
+        if let Some(chips) = working_chips {
+            board.working_chips = Some(chips as u16);
+        }
+        if let Some(rate) = rate {
+            board.hashrate = Some(HashRate {
+                value: rate,
+                unit: HashRateUnit::GigaHash,
+                algo: data.device_info.algo.to_string(),
+            });
+            board.active = Some(rate > 0.0);
+        }
+        if let Some(max) = pcb_temps.iter().copied().reduce(f64::max) {
+            board.board_temperature = Some(Temperature::from_celsius(max));
+        }
+    }
+}
+
+/// The summed hashrate of a set of boards, for firmware whose `summary` could not be read
+pub(crate) fn total_board_hashrate(hashboards: &[BoardData]) -> Option<HashRate> {
+    let rates: Vec<&HashRate> = hashboards
+        .iter()
+        .filter_map(|b| b.hashrate.as_ref())
+        .collect();
+    let algo = rates.first()?.algo.clone();
+    Some(HashRate {
+        value: rates
+            .iter()
+            .map(|hr| hr.as_unit(HashRateUnit::GigaHash).value)
+            .sum(),
+        unit: HashRateUnit::GigaHash,
+        algo,
+    })
+}
+
+/// Parse a reading which some firmware report as a string, such as `"13500.25"`
+pub(crate) fn parse_number(value: &Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str()?.trim().parse().ok())
+}
+
+/// Parse a temperature list, either a number or a string of sensor readings like `"55-58-60-57"`
+fn parse_temps(value: &Value) -> Vec<f64> {
+    match value {
+        Value::Number(n) => n.as_f64().into_iter().filter(|t| *t > 0.0).collect(),
+        Value::String(s) => s
+            .split('-')
+            .filter_map(|t| t.trim().parse::<f64>().ok())
+            .filter(|t| *t > 0.0)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::device::models::volcminer::VolcminerModel;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_antminer_stats() {
+        let model = MinerModel::Volcminer(VolcminerModel::D1);
+        let mut data = MinerData {
+            schema_version: String::new(),
+            timestamp: 0,
+            ip: IpAddr::from([10, 0, 0, 1]),
+            target_name: None,
+            mac: None,
+            device_info: DeviceInfo::new(
+                MinerMake::Volcminer,
+                model,
+                MinerFirmware::Stock,
+                HashAlgorithm::Scrypt,
+            ),
+            serial_number: None,
+            hostname: None,
+            api_version: None,
+            firmware_version: None,
+            control_board_version: None,
+            expected_hashboards: None,
+            hashboards: vec![],
+            hashrate: None,
+            expected_chips: None,
+            total_chips: None,
+            expected_fans: None,
+            fans: vec![],
+            psu_fans: vec![],
+            average_temperature: None,
+            fluid_temperature: None,
+            wattage: None,
+            wattage_limit: None,
+            efficiency: None,
+            light_flashing: None,
+            messages: vec![],
+            uptime: None,
+            is_mining: false,
+            pools: vec![],
+            location: None,
+        };
+        let stats = json!({"STATS": [
+            {"CGMiner": "4.11.1"},
+            {"fan1": 5400, "fan2": 5460, "chain_acn1": 80, "temp_chip1": "55-58-61-63",
+             "temp_pcb1": "45-47-50-49", "chain_rate1": "4512.33", "chain_acn2": 79,
+             "chain_rate2": 0}
+        ]});
+
+        StockCGMinerAdapter(MinerMake::Volcminer).apply_stats(&stats, &mut data);
+
+        assert_eq!(data.fans.len(), 2);
+        assert_eq!(data.hashboards.len(), 2);
+        assert_eq!(data.hashboards[0].working_chips, Some(80));
+        assert_eq!(data.hashboards[0].intake_temperature, None);
+        assert_eq!(data.hashboards[0].outlet_temperature, None);
+        assert_eq!(
+            data.hashboards[0].hashrate.as_ref().map(|hr| hr.value),
+            Some(4512.33)
+        );
+        assert_eq!(data.hashboards[0].active, Some(true));
+        assert_eq!(
+            data.hashboards[0].board_temperature,
+            Some(Temperature::from_celsius(50.0))
+        );
+        assert_eq!(data.hashboards[1].position, 1);
+        assert_eq!(data.hashboards[1].active, Some(false));
+    }
+
+    #[test]
+    fn test_parse_hashrate_antminer_summary() {
+        let summary = json!({"STATUS": [{"STATUS": "S", "Msg": "Summary"}], "SUMMARY": [
+            {"Elapsed": 86400, "GHS 5s": "13500.25", "GHS av": 13480.12, "Accepted": 9812}
+        ]});
+
+        let hashrate = parse_hashrate(&summary["SUMMARY"][0], &HashAlgorithm::SHA256)
+            .expect("GHS 5s should be parsed");
+        assert_eq!(hashrate.value, 13500.25);
+        assert_eq!(hashrate.unit, HashRateUnit::GigaHash);
+
+        let cgminer = json!({"MHS 5s": 9500.0, "MHS av": 9480.0});
+        let hashrate = parse_hashrate(&cgminer, &HashAlgorithm::Scrypt).unwrap();
+        assert_eq!(hashrate.value, 9500.0);
+        assert_eq!(hashrate.unit, HashRateUnit::MegaHash);
+    }
+
+    #[test]
+    fn test_adapter_for_unknown_make() {
+        let adapter = CGMiner::adapter_for(MinerMake::AvalonMiner);
+        assert_eq!(adapter.make(), MinerMake::AvalonMiner);
+        assert_eq!(adapter.algo(), HashAlgorithm::SHA256);
+        assert_eq!(
+            CGMiner::adapter_for(MinerMake::Hammer).algo(),
+            HashAlgorithm::Scrypt
+        );
+    }
+}
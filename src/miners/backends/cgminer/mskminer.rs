@@ -0,0 +1,98 @@
+use measurements::{Frequency, Power};
+use serde_json::Value;
+
+use super::{CGMinerAdapter, apply_antminer_stats, parse_number, total_board_hashrate};
+use crate::data::device::{MinerFirmware, MinerMake};
+use crate::data::hashrate::{HashRate, HashRateUnit};
+use crate::data::miner::MinerData;
+
+/// Adapter for AntMiners running MSKMiner firmware, which extends the AntMiner `stats`
+/// fields with power, expected hashrate and frequency readings
+pub struct MSKMinerAdapter;
+
+impl CGMinerAdapter for MSKMinerAdapter {
+    fn make(&self) -> MinerMake {
+        MinerMake::AntMiner
+    }
+
+    fn firmware(&self) -> MinerFirmware {
+        MinerFirmware::MSKMiner
+    }
+
+    fn apply_stats(&self, stats: &Value, data: &mut MinerData) {
+        apply_antminer_stats(stats, data);
+
+        let Some(stats) = stats["STATS"].as_array().and_then(|s| s.last()) else {
+            return;
+        };
+
+        data.wattage = parse_number(&stats["total_power"]).map(Power::from_watts);
+        for board in data.hashboards.iter_mut() {
+            let chain = board.position + 1;
+            if let Some(ideal) = parse_number(&stats[format!("chain_rateideal{}", chain)]) {
+                board.expected_hashrate = Some(HashRate {
+                    value: ideal,
+                    unit: HashRateUnit::GigaHash,
+                    algo: String::from("SHA256"),
+                });
+            }
+            if let Some(freq) = parse_number(&stats[format!("freq_avg{}", chain)]) {
+                board.frequency = Some(Frequency::from_megahertz(freq));
+            }
+        }
+
+        // `summary` is the usual source, fall back to the `chain_rate` readings without it
+        if data.hashrate.is_none() {
+            data.hashrate = total_board_hashrate(&data.hashboards);
+        }
+        data.efficiency = match (&data.hashrate, data.wattage) {
+            (Some(hr), Some(w)) if hr.value > 0.0 => Some(w / hr.as_unit(HashRateUnit::TeraHash)),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::net::IpAddr;
+
+    #[test]
+    fn test_apply_stats() {
+        let mut data = MinerData::empty(IpAddr::from([10, 0, 0, 1]));
+        let stats = json!({"STATS": [
+            {"BMMiner": "2.0.0", "Miner": "MSKMiner 1.3.2"},
+            {"fan1": 5880, "fan2": 5820, "total_power": "3250",
+             "chain_acn1": 76, "chain_rate1": "31200.5", "chain_rateideal1": 31000,
+             "freq_avg1": "525.00", "temp_pcb1": "52-55-58-54",
+             "chain_acn2": 76, "chain_rate2": 31050.25, "chain_rateideal2": "31000",
+             "freq_avg2": 520}
+        ]});
+
+        MSKMinerAdapter.apply_stats(&stats, &mut data);
+
+        assert_eq!(data.wattage, Some(Power::from_watts(3250.0)));
+        assert_eq!(data.hashboards.len(), 2);
+        let board = &data.hashboards[0];
+        assert_eq!(board.hashrate.as_ref().map(|hr| hr.value), Some(31200.5));
+        assert_eq!(
+            board.expected_hashrate.as_ref().map(|hr| hr.value),
+            Some(31000.0)
+        );
+        assert_eq!(board.frequency, Some(Frequency::from_megahertz(525.0)));
+        assert_eq!(
+            data.hashboards[1].frequency,
+            Some(Frequency::from_megahertz(520.0))
+        );
+
+        // without `summary` the hashrate comes from the chains, 62.25 TH/s at 3250 W
+        let hashrate = data
+            .hashrate
+            .as_ref()
+            .expect("chain rates should be summed");
+        assert_eq!(hashrate.as_unit(HashRateUnit::GigaHash).value, 62250.75);
+        let efficiency = data.efficiency.expect("efficiency should be computed");
+        assert!((efficiency - 3250.0 / 62.25075).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,28 @@
+use serde_json::Value;
+
+use super::{CGMinerAdapter, apply_antminer_stats};
+use crate::data::device::{HashAlgorithm, MinerMake};
+use crate::data::miner::MinerData;
+
+/// Adapter for makes running their stock CGMiner, which use AntMiner style `stats` fields.
+///
+/// Elphapex, Volcminer and Hammer miners are known to work with it, and it is the fallback
+/// for any other make without a backend of its own.
+pub struct StockCGMinerAdapter(pub MinerMake);
+
+impl CGMinerAdapter for StockCGMinerAdapter {
+    fn make(&self) -> MinerMake {
+        self.0
+    }
+
+    fn algo(&self) -> HashAlgorithm {
+        match self.0 {
+            MinerMake::Elphapex | MinerMake::Volcminer | MinerMake::Hammer => HashAlgorithm::Scrypt,
+            _ => HashAlgorithm::SHA256,
+        }
+    }
+
+    fn apply_stats(&self, stats: &Value, data: &mut MinerData) {
+        apply_antminer_stats(stats, data);
+    }
+}
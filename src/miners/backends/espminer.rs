@@ -5,45 +5,71 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use async_trait::async_trait;
 use macaddr::MacAddr;
 use measurements::{AngularVelocity, Frequency, Power, Temperature, Voltage};
+use serde_json::Value;
 
 use crate::data::board::{BoardData, ChipData};
 use crate::data::device::MinerMake::BitAxe;
 use crate::data::device::{DeviceInfo, HashAlgorithm, MinerFirmware, MinerHardware, MinerModel};
 use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
 use crate::data::hashrate::{HashRate, HashRateUnit};
 use crate::data::message::{MessageSeverity, MinerMessage};
 use crate::data::miner::MinerData;
 use crate::data::pool::{PoolData, PoolScheme, PoolURL};
+use crate::miners::api::web::WebClient;
 use crate::miners::api::web::esp_web_api::EspWebApi;
-use crate::miners::backends::traits::GetMinerData;
+use crate::miners::backends::traits::{GetMinerData, RawCommandError, raw_web_method};
+use crate::miners::commands::MinerCommand;
 use crate::miners::data::{
     DataCollector, DataExtensions, DataExtractor, DataField, DataLocation, get_by_key,
     get_by_pointer,
 };
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
+
+const ASIC_INFO_CMD: &str = "system/asic";
 
 pub struct ESPMiner {
     model: MinerModel,
     web: EspWebApi,
     ip: IpAddr,
+    target: MinerTarget,
     firmware: MinerFirmware,
 }
 
 impl ESPMiner {
-    pub fn new(ip: IpAddr, model: MinerModel, miner_firmware: MinerFirmware) -> Self {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(
+        target: impl Into<MinerTarget>,
+        model: MinerModel,
+        miner_firmware: MinerFirmware,
+    ) -> Self {
+        let target = target.into();
+        let ports = target.ports(MinerPorts::new());
         ESPMiner {
             model,
-            web: EspWebApi::new(ip.to_string(), 80),
-            ip,
+            web: EspWebApi::new(target.host().to_owned(), 80),
+            ip: target.data_ip(),
+            target,
             firmware: miner_firmware,
         }
+        .with_ports(ports)
+    }
+
+    /// Use non-standard ports or HTTPS for the web API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.web = EspWebApi::new(self.target.host().to_owned(), ports.web_port(80))
+            .with_https(ports.use_https);
+        self
     }
 }
 
 #[async_trait]
 impl GetMinerData for ESPMiner {
-    async fn get_data(&self) -> MinerData {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
         let mut collector = DataCollector::new(self, &self.web);
-        let data = collector.collect_all().await;
+        collector.fetch(fields).await;
+        let data = collector.extract(fields);
 
         // Extract basic string fields
         let mac = data
@@ -51,8 +77,12 @@ impl GetMinerData for ESPMiner {
             .and_then(|s| MacAddr::from_str(&s).ok());
 
         let hostname = data.extract::<String>(DataField::Hostname);
-        let api_version = data.extract::<String>(DataField::ApiVersion);
-        let firmware_version = data.extract::<String>(DataField::FirmwareVersion);
+        let api_version = data
+            .extract::<String>(DataField::ApiVersion)
+            .map(|v| FirmwareVersion::parse_api(&v));
+        let firmware_version = data
+            .extract::<String>(DataField::FirmwareVersion)
+            .map(|v| FirmwareVersion::parse(self.firmware, &v));
         let control_board_version = data.extract::<String>(DataField::ControlBoardVersion);
 
         // Extract hashrate and convert to HashRate structure
@@ -98,7 +128,9 @@ impl GetMinerData for ESPMiner {
         // Get hardware specifications based on the miner model
         let miner_hardware = MinerHardware::from(&self.model);
 
-        let hashboards = {
+        let hashboards = if !data.contains_key(&DataField::Hashboards) {
+            vec![]
+        } else {
             // Extract nested values with type conversion
             let board_voltage = data.extract_nested_map::<f64, _>(
                 DataField::Hashboards,
@@ -136,14 +168,29 @@ impl GetMinerData for ESPMiner {
 
             let board_hashrate = hashrate.clone();
 
-            let chip_info = ChipData {
-                position: 0,
-                temperature: chip_temperature,
-                voltage: board_voltage,
-                frequency: board_frequency,
-                tuned: Some(true),
-                working: Some(true),
-                hashrate: board_hashrate.clone(),
+            let chip_count = total_chips.or(miner_hardware.chips).unwrap_or(1);
+            let chips = if chip_count > 1 {
+                // multi-ASIC boards report per chip data in `system/asic`, older firmware only
+                // has the per chip hashrate monitor in `system/info`
+                let asics = collector
+                    .response(ASIC_INFO_CMD)
+                    .and_then(|info| info["asics"].as_array())
+                    .or_else(|| {
+                        data.get(&DataField::Hashboards)
+                            .and_then(|info| info.pointer("/hashrateMonitor/asics"))
+                            .and_then(|asics| asics.as_array())
+                    });
+                parse_chips(asics, chip_count, board_voltage, board_frequency)
+            } else {
+                vec![ChipData {
+                    position: 0,
+                    temperature: chip_temperature,
+                    voltage: board_voltage,
+                    frequency: board_frequency,
+                    tuned: Some(true),
+                    working: Some(true),
+                    hashrate: board_hashrate.clone(),
+                }]
             };
 
             let board_data = BoardData {
@@ -154,9 +201,13 @@ impl GetMinerData for ESPMiner {
                 intake_temperature: board_temperature,
                 outlet_temperature: board_temperature,
                 expected_chips: miner_hardware.chips,
-                working_chips: total_chips,
+                working_chips: if chips.iter().any(|c| c.working.is_some()) {
+                    Some(chips.iter().filter(|c| c.working == Some(true)).count() as u16)
+                } else {
+                    total_chips
+                },
                 serial_number: None,
-                chips: vec![chip_info],
+                chips,
                 voltage: board_voltage,
                 frequency: board_frequency,
                 tuned: Some(true),
@@ -166,7 +217,9 @@ impl GetMinerData for ESPMiner {
             vec![board_data]
         };
 
-        let pools = {
+        let pools = if !data.contains_key(&DataField::Pools) {
+            vec![]
+        } else {
             let main_url =
                 data.extract_nested_or::<String>(DataField::Pools, "stratumURL", String::new());
             let main_port = data.extract_nested_or::<u64>(DataField::Pools, "stratumPort", 0);
@@ -225,7 +278,7 @@ impl GetMinerData for ESPMiner {
 
         let mut messages = Vec::new();
 
-        let is_overheating = data.extract_nested::<bool>(DataField::Hashboards, "overheat_mode");
+        let is_overheating = data.extract_nested::<bool>(DataField::Messages, "overheat_mode");
 
         if let Some(true) = is_overheating {
             messages.push(MinerMessage {
@@ -243,6 +296,7 @@ impl GetMinerData for ESPMiner {
 
             // Network identification
             ip: self.ip,
+            target_name: self.target.hostname().map(String::from),
             mac,
 
             // Device identification
@@ -288,12 +342,12 @@ impl GetMinerData for ESPMiner {
             is_mining,
 
             pools,
+            location: None,
         }
     }
 
     fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
         const SYSTEM_INFO_CMD: &str = "system/info";
-        const ASIC_INFO_CMD: &str = "system/asic";
 
         match data_field {
             DataField::Mac => &[(
@@ -324,7 +378,25 @@ impl GetMinerData for ESPMiner {
                     key: Some("boardVersion"),
                 },
             )],
-            DataField::Hashboards => &[(
+            // the board is read from system info, with per chip data from the collector's
+            // system/asic response
+            DataField::Hashboards => &[
+                (
+                    SYSTEM_INFO_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some(""),
+                    },
+                ),
+                (
+                    ASIC_INFO_CMD,
+                    DataExtractor {
+                        func: get_by_pointer,
+                        key: Some(""),
+                    },
+                ),
+            ],
+            DataField::Messages => &[(
                 SYSTEM_INFO_CMD,
                 DataExtractor {
                     func: get_by_pointer,
@@ -392,4 +464,85 @@ impl GetMinerData for ESPMiner {
             _ => &[],
         }
     }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::WebAPI { command } => {
+                let method = raw_web_method(&params);
+                self.web
+                    .send_command(command, false, params, method)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
+    }
+}
+
+/// Build the chip list for a multi-ASIC board, using per chip readings where available and the
+/// board voltage and frequency set points otherwise
+fn parse_chips(
+    asics: Option<&Vec<Value>>,
+    chip_count: u16,
+    board_voltage: Option<Voltage>,
+    board_frequency: Option<Frequency>,
+) -> Vec<ChipData> {
+    (0..chip_count)
+        .map(|idx| {
+            let asic = asics.and_then(|asics| asics.get(idx as usize));
+            let field = |key: &str| asic.and_then(|a| a[key].as_f64());
+
+            let hashrate = field("total").or(field("hashrate")).map(|f| HashRate {
+                value: f,
+                unit: HashRateUnit::GigaHash,
+                algo: String::from("SHA256"),
+            });
+
+            ChipData {
+                position: idx,
+                working: hashrate.as_ref().map(|hr| hr.value > 0.0),
+                hashrate,
+                temperature: field("temp").map(Temperature::from_celsius),
+                voltage: field("voltage")
+                    .map(Voltage::from_millivolts)
+                    .or(board_voltage),
+                frequency: field("frequency")
+                    .map(Frequency::from_megahertz)
+                    .or(board_frequency),
+                tuned: Some(true),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_chips() {
+        let asics = json!([
+            {"total": 1210.5, "temp": 61.0, "frequency": 525.0},
+            {"total": 0.0, "temp": 58.5},
+        ]);
+        let chips = parse_chips(
+            asics.as_array(),
+            4,
+            Some(Voltage::from_millivolts(1150.0)),
+            Some(Frequency::from_megahertz(500.0)),
+        );
+
+        assert_eq!(chips.len(), 4);
+        assert_eq!(chips[0].working, Some(true));
+        assert_eq!(chips[0].frequency, Some(Frequency::from_megahertz(525.0)));
+        assert_eq!(chips[1].working, Some(false));
+        assert_eq!(chips[1].frequency, Some(Frequency::from_megahertz(500.0)));
+        assert_eq!(chips[3].working, None);
+        assert_eq!(chips[3].voltage, Some(Voltage::from_millivolts(1150.0)));
+    }
 }
@@ -0,0 +1,446 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use macaddr::MacAddr;
+use measurements::{AngularVelocity, Temperature};
+use serde_json::Value;
+
+use crate::data::board::BoardData;
+use crate::data::device::MinerMake::Goldshell as GoldshellMake;
+use crate::data::device::{DeviceInfo, HashAlgorithm, MinerFirmware, MinerHardware, MinerModel};
+use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
+use crate::data::hashrate::{HashRate, HashRateUnit};
+use crate::data::miner::MinerData;
+use crate::data::pool::{PoolData, PoolURL};
+use crate::miners::api::web::WebClient;
+use crate::miners::api::web::goldshell_web_api::GoldshellWebApi;
+use crate::miners::backends::traits::{GetMinerData, RawCommandError, raw_web_method};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{
+    DataCollector, DataExtensions, DataExtractor, DataField, DataLocation, get_by_key,
+    get_by_pointer,
+};
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
+
+pub struct Goldshell {
+    model: MinerModel,
+    web: GoldshellWebApi,
+    ip: IpAddr,
+    target: MinerTarget,
+}
+
+impl Goldshell {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(target: impl Into<MinerTarget>, model: MinerModel) -> Self {
+        let target = target.into();
+        let ports = target.ports(MinerPorts::new());
+        Goldshell {
+            model,
+            web: GoldshellWebApi::new(target.host().to_owned(), 80),
+            ip: target.data_ip(),
+            target,
+        }
+        .with_ports(ports)
+    }
+
+    /// Use non-standard ports or HTTPS for the web API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.web = GoldshellWebApi::new(self.target.host().to_owned(), ports.web_port(80))
+            .with_https(ports.use_https);
+        self
+    }
+
+    fn algo(&self) -> HashAlgorithm {
+        match &self.model {
+            MinerModel::Goldshell(model) => model.algo(),
+            _ => HashAlgorithm::Scrypt,
+        }
+    }
+}
+
+#[async_trait]
+impl GetMinerData for Goldshell {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut collector = DataCollector::new(self, &self.web);
+        let data = collector.collect(fields).await;
+
+        let algo = self.algo();
+        let miner_hardware = MinerHardware::from(&self.model);
+
+        let mac = data
+            .extract::<String>(DataField::Mac)
+            .and_then(|s| MacAddr::from_str(&s).ok());
+        let hostname = data.extract::<String>(DataField::Hostname);
+        let firmware_version = data
+            .extract::<String>(DataField::FirmwareVersion)
+            .map(|v| FirmwareVersion::parse(MinerFirmware::Stock, &v));
+        let control_board_version = data.extract::<String>(DataField::ControlBoardVersion);
+        let uptime = data.extract_map::<u64, _>(DataField::Uptime, Duration::from_secs);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get system time")
+            .as_secs();
+
+        let hashboards: Vec<BoardData> = data
+            .get(&DataField::Hashboards)
+            .and_then(|v| v.as_array())
+            .map(|boards| {
+                boards
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, board)| parse_board(idx, board, &algo, miner_hardware.chips))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let hashrate = {
+            let rates: Vec<f64> = hashboards
+                .iter()
+                .filter_map(|b| b.hashrate.as_ref().map(|hr| hr.value))
+                .collect();
+            if rates.is_empty() {
+                None
+            } else {
+                Some(HashRate {
+                    value: rates.iter().sum(),
+                    unit: HashRateUnit::MegaHash,
+                    algo: algo.to_string(),
+                })
+            }
+        };
+
+        let fans = data
+            .get(&DataField::Fans)
+            .and_then(|v| v.as_array())
+            .map(|fans| {
+                fans.iter()
+                    .enumerate()
+                    .filter_map(|(idx, rpm)| {
+                        Some(FanData {
+                            position: idx as i16,
+                            rpm: AngularVelocity::from_rpm(rpm.as_f64()?),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let average_temperature = {
+            let temps: Vec<f64> = hashboards
+                .iter()
+                .filter_map(|b| b.outlet_temperature.or(b.board_temperature))
+                .map(|t| t.as_celsius())
+                .collect();
+            if temps.is_empty() {
+                None
+            } else {
+                Some(Temperature::from_celsius(
+                    temps.iter().sum::<f64>() / temps.len() as f64,
+                ))
+            }
+        };
+
+        let pools = data
+            .get(&DataField::Pools)
+            .and_then(|v| v.as_array())
+            .map(|pools| pools.iter().enumerate().map(parse_pool).collect())
+            .unwrap_or_default();
+
+        let total_chips = hashboards
+            .iter()
+            .map(|b| b.working_chips)
+            .sum::<Option<u16>>();
+        let is_mining = hashrate.as_ref().is_some_and(|hr| hr.value > 0.0);
+
+        MinerData {
+            schema_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp,
+            ip: self.ip,
+            target_name: self.target.hostname().map(String::from),
+            mac,
+            device_info: DeviceInfo::new(
+                GoldshellMake,
+                self.model.clone(),
+                MinerFirmware::Stock,
+                algo,
+            ),
+            serial_number: None,
+            hostname,
+            api_version: None,
+            firmware_version,
+            control_board_version,
+            expected_hashboards: miner_hardware.boards,
+            hashboards,
+            hashrate,
+            expected_chips: match (miner_hardware.chips, miner_hardware.boards) {
+                (Some(chips), Some(boards)) => Some(chips * boards as u16),
+                _ => None,
+            },
+            total_chips,
+            expected_fans: miner_hardware.fans,
+            fans,
+            psu_fans: vec![],
+            average_temperature,
+            fluid_temperature: None,
+            wattage: None,
+            wattage_limit: None,
+            efficiency: None,
+            light_flashing: data.extract::<bool>(DataField::LightFlashing),
+            messages: vec![],
+            uptime,
+            is_mining,
+            pools,
+            location: None,
+        }
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        const STATUS_CMD: &str = "status";
+        const SETTING_CMD: &str = "setting";
+        const DEVS_CMD: &str = "cgminer?cgminercmd=devs";
+        const POOLS_CMD: &str = "pools";
+
+        match data_field {
+            DataField::Mac => &[(
+                STATUS_CMD,
+                DataExtractor {
+                    func: get_by_key,
+                    key: Some("mac"),
+                },
+            )],
+            DataField::Hostname => &[(
+                SETTING_CMD,
+                DataExtractor {
+                    func: get_by_key,
+                    key: Some("name"),
+                },
+            )],
+            DataField::FirmwareVersion => &[(
+                STATUS_CMD,
+                DataExtractor {
+                    func: get_by_key,
+                    key: Some("firmware"),
+                },
+            )],
+            DataField::ControlBoardVersion => &[(
+                STATUS_CMD,
+                DataExtractor {
+                    func: get_by_key,
+                    key: Some("hardware"),
+                },
+            )],
+            DataField::Uptime => &[(
+                STATUS_CMD,
+                DataExtractor {
+                    func: get_by_key,
+                    key: Some("uptime"),
+                },
+            )],
+            DataField::Fans => &[(
+                STATUS_CMD,
+                DataExtractor {
+                    func: get_by_key,
+                    key: Some("fanspeed"),
+                },
+            )],
+            DataField::LightFlashing => &[(
+                SETTING_CMD,
+                DataExtractor {
+                    func: get_by_key,
+                    key: Some("ledcontrol"),
+                },
+            )],
+            DataField::Hashboards => &[(
+                DEVS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data"),
+                },
+            )],
+            DataField::Pools => &[(
+                POOLS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some(""),
+                },
+            )],
+            _ => &[],
+        }
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::WebAPI { command } => {
+                let method = raw_web_method(&params);
+                self.web
+                    .send_command(command, params, method)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
+    }
+}
+
+fn parse_board(
+    idx: usize,
+    board: &Value,
+    algo: &HashAlgorithm,
+    expected_chips: Option<u16>,
+) -> BoardData {
+    let hashrate = board["MHS 20s"]
+        .as_f64()
+        .or(board["MHS av"].as_f64())
+        .map(|f| HashRate {
+            value: f,
+            unit: HashRateUnit::MegaHash,
+            algo: algo.to_string(),
+        });
+
+    BoardData {
+        position: board["ID"].as_u64().unwrap_or(idx as u64) as u8,
+        active: Some(hashrate.as_ref().is_some_and(|hr| hr.value > 0.0)),
+        hashrate,
+        expected_hashrate: None,
+        board_temperature: board["temp"].as_f64().map(Temperature::from_celsius),
+        intake_temperature: board["tstemp-1"].as_f64().map(Temperature::from_celsius),
+        outlet_temperature: board["tstemp-2"].as_f64().map(Temperature::from_celsius),
+        expected_chips,
+        working_chips: board["chips"].as_u64().map(|u| u as u16),
+        serial_number: None,
+        chips: vec![],
+        voltage: None,
+        frequency: None,
+        tuned: None,
+    }
+}
+
+fn parse_pool((idx, pool): (usize, &Value)) -> PoolData {
+    PoolData {
+        position: Some(pool["pool-priority"].as_u64().unwrap_or(idx as u64) as u16),
+        url: pool["url"].as_str().and_then(PoolURL::parse),
+        accepted_shares: pool["accepted"].as_u64(),
+        rejected_shares: pool["rejected"].as_u64(),
+        active: pool["active"].as_bool(),
+        alive: pool["status"]
+            .as_str()
+            .map(|s| s.eq_ignore_ascii_case("alive")),
+        user: pool["user"].as_str().map(String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::device::models::goldshell::GoldshellModel;
+    use crate::miners::api::fixture::FixtureClient;
+    use serde_json::json;
+
+    /// Responses in the shape KD5 firmware returns, one board mining and one idle
+    fn client() -> FixtureClient {
+        FixtureClient::new([
+            (
+                "status",
+                json!({
+                    "model": "Goldshell-KD5", "firmware": "2.2.5", "hardware": "GS-CB-V2",
+                    "mac": "c4:08:28:00:11:22", "uptime": 86400, "fanspeed": [4320, 4410]
+                }),
+            ),
+            (
+                "cgminer?cgminercmd=devs",
+                json!({
+                    "data": [
+                        {"ID": 0, "MHS 20s": 6012345.5, "MHS av": 6001234.0, "temp": 52.0,
+                         "tstemp-1": 45.5, "tstemp-2": 61.0, "chips": 168},
+                        {"ID": 1, "MHS 20s": 0.0, "MHS av": 0.0, "temp": 40.0, "chips": 0}
+                    ]
+                }),
+            ),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_status_locations() {
+        let miner = Goldshell::new(
+            IpAddr::from([10, 0, 0, 1]),
+            MinerModel::Goldshell(GoldshellModel::KD5),
+        );
+        let client = client();
+        let mut collector = DataCollector::new(&miner, &client);
+
+        let data = collector
+            .collect(&[
+                DataField::Mac,
+                DataField::FirmwareVersion,
+                DataField::ControlBoardVersion,
+                DataField::Uptime,
+                DataField::Fans,
+            ])
+            .await;
+
+        assert_eq!(
+            data.extract::<String>(DataField::Mac).as_deref(),
+            Some("c4:08:28:00:11:22")
+        );
+        assert_eq!(
+            data.extract::<String>(DataField::FirmwareVersion)
+                .as_deref(),
+            Some("2.2.5")
+        );
+        assert_eq!(
+            data.extract::<String>(DataField::ControlBoardVersion)
+                .as_deref(),
+            Some("GS-CB-V2")
+        );
+        assert_eq!(data.extract::<u64>(DataField::Uptime), Some(86400));
+        assert_eq!(
+            data.extract::<Vec<f64>>(DataField::Fans),
+            Some(vec![4320.0, 4410.0])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_devs() {
+        let miner = Goldshell::new(
+            IpAddr::from([10, 0, 0, 1]),
+            MinerModel::Goldshell(GoldshellModel::KD5),
+        );
+        let client = client();
+        let mut collector = DataCollector::new(&miner, &client);
+        let data = collector.collect(&[DataField::Hashboards]).await;
+        let boards = data[&DataField::Hashboards].as_array().unwrap();
+
+        let board = parse_board(0, &boards[0], &HashAlgorithm::Blake2S256, Some(168));
+        assert_eq!(board.position, 0);
+        assert_eq!(board.active, Some(true));
+        assert_eq!(board.hashrate.map(|hr| hr.value), Some(6012345.5));
+        assert_eq!(
+            board.board_temperature,
+            Some(Temperature::from_celsius(52.0))
+        );
+        assert_eq!(
+            board.intake_temperature,
+            Some(Temperature::from_celsius(45.5))
+        );
+        assert_eq!(
+            board.outlet_temperature,
+            Some(Temperature::from_celsius(61.0))
+        );
+        assert_eq!(board.working_chips, Some(168));
+
+        let board = parse_board(1, &boards[1], &HashAlgorithm::Blake2S256, Some(168));
+        assert_eq!(board.position, 1);
+        assert_eq!(board.active, Some(false));
+        assert_eq!(board.outlet_temperature, None);
+        assert_eq!(board.working_chips, Some(0));
+    }
+}
@@ -0,0 +1,419 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use macaddr::MacAddr;
+use measurements::{AngularVelocity, Frequency, Temperature};
+use serde_json::Value;
+
+use crate::data::board::BoardData;
+use crate::data::device::MinerMake::IceRiver as IceRiverMake;
+use crate::data::device::{DeviceInfo, HashAlgorithm, MinerFirmware, MinerHardware, MinerModel};
+use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
+use crate::data::hashrate::{HashRate, HashRateUnit};
+use crate::data::miner::MinerData;
+use crate::data::pool::{PoolData, PoolURL};
+use crate::miners::api::web::WebClient;
+use crate::miners::api::web::iceriver_web_api::IceRiverWebApi;
+use crate::miners::backends::traits::{GetMinerData, RawCommandError};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{
+    DataCollector, DataExtensions, DataExtractor, DataField, DataLocation, get_by_pointer,
+};
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
+
+pub struct IceRiver {
+    model: MinerModel,
+    web: IceRiverWebApi,
+    ip: IpAddr,
+    target: MinerTarget,
+}
+
+impl IceRiver {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(target: impl Into<MinerTarget>, model: MinerModel) -> Self {
+        let target = target.into();
+        let ports = target.ports(MinerPorts::new());
+        IceRiver {
+            model,
+            web: IceRiverWebApi::new(target.host().to_owned(), 80),
+            ip: target.data_ip(),
+            target,
+        }
+        .with_ports(ports)
+    }
+
+    /// Use non-standard ports or HTTPS for the web API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.web = IceRiverWebApi::new(self.target.host().to_owned(), ports.web_port(80))
+            .with_https(ports.use_https);
+        self
+    }
+}
+
+#[async_trait]
+impl GetMinerData for IceRiver {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut collector = DataCollector::new(self, &self.web);
+        let data = collector.collect(fields).await;
+
+        let miner_hardware = MinerHardware::from(&self.model);
+
+        let mac = data
+            .extract::<String>(DataField::Mac)
+            .and_then(|s| MacAddr::from_str(&s).ok());
+        let hostname = data.extract::<String>(DataField::Hostname);
+        let firmware_version = data
+            .extract::<String>(DataField::FirmwareVersion)
+            .map(|v| FirmwareVersion::parse(MinerFirmware::Stock, &v));
+        let uptime = data
+            .extract::<String>(DataField::Uptime)
+            .and_then(|s| parse_runtime(&s));
+        let light_flashing = data.extract::<bool>(DataField::LightFlashing);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get system time")
+            .as_secs();
+
+        // the total hashrate is reported without a unit, which is in a separate field
+        let hashrate = data
+            .get(&DataField::Hashrate)
+            .and_then(|panel| parse_hashrate(&panel["rtpow"], panel["unit"].as_str()));
+
+        let hashboards: Vec<BoardData> = data
+            .get(&DataField::Hashboards)
+            .and_then(|v| v.as_array())
+            .map(|boards| {
+                boards
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, board)| parse_board(idx, board, miner_hardware.chips))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let fans = data
+            .get(&DataField::Fans)
+            .and_then(|v| v.as_array())
+            .map(|fans| {
+                fans.iter()
+                    .enumerate()
+                    .filter_map(|(idx, rpm)| {
+                        Some(FanData {
+                            position: idx as i16,
+                            rpm: AngularVelocity::from_rpm(rpm.as_f64()?),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let average_temperature = {
+            let temps: Vec<f64> = hashboards
+                .iter()
+                .filter_map(|b| b.outlet_temperature)
+                .map(|t| t.as_celsius())
+                .collect();
+            if temps.is_empty() {
+                None
+            } else {
+                Some(Temperature::from_celsius(
+                    temps.iter().sum::<f64>() / temps.len() as f64,
+                ))
+            }
+        };
+
+        let pools = data
+            .get(&DataField::Pools)
+            .and_then(|v| v.as_array())
+            .map(|pools| pools.iter().enumerate().map(parse_pool).collect())
+            .unwrap_or_default();
+
+        let total_chips = hashboards
+            .iter()
+            .map(|b| b.working_chips)
+            .sum::<Option<u16>>();
+        let is_mining = data
+            .extract::<bool>(DataField::IsMining)
+            .unwrap_or_else(|| hashrate.as_ref().is_some_and(|hr| hr.value > 0.0));
+
+        MinerData {
+            schema_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp,
+            ip: self.ip,
+            target_name: self.target.hostname().map(String::from),
+            mac,
+            device_info: DeviceInfo::new(
+                IceRiverMake,
+                self.model.clone(),
+                MinerFirmware::Stock,
+                HashAlgorithm::KHeavyHash,
+            ),
+            serial_number: None,
+            hostname,
+            api_version: None,
+            firmware_version,
+            control_board_version: None,
+            expected_hashboards: miner_hardware.boards,
+            hashboards,
+            hashrate,
+            expected_chips: match (miner_hardware.chips, miner_hardware.boards) {
+                (Some(chips), Some(boards)) => Some(chips * boards as u16),
+                _ => None,
+            },
+            total_chips,
+            expected_fans: miner_hardware.fans,
+            fans,
+            psu_fans: vec![],
+            average_temperature,
+            fluid_temperature: None,
+            wattage: None,
+            wattage_limit: None,
+            efficiency: None,
+            light_flashing,
+            messages: vec![],
+            uptime,
+            is_mining,
+            pools,
+            location: None,
+        }
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        const USERPANEL_CMD: &str = "userpanel";
+
+        match data_field {
+            DataField::Mac => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/mac"),
+                },
+            )],
+            DataField::Hostname => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/host"),
+                },
+            )],
+            DataField::FirmwareVersion => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/softver1"),
+                },
+            )],
+            DataField::Uptime => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/runtime"),
+                },
+            )],
+            DataField::Hashrate => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data"),
+                },
+            )],
+            DataField::Hashboards => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/boards"),
+                },
+            )],
+            DataField::Fans => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/fans"),
+                },
+            )],
+            DataField::LightFlashing => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/locate"),
+                },
+            )],
+            DataField::IsMining => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/powstate"),
+                },
+            )],
+            DataField::Pools => &[(
+                USERPANEL_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/data/pools"),
+                },
+            )],
+            _ => &[],
+        }
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::WebAPI { command } => {
+                // commands are form posts, so parameters are sent as form fields
+                let fields: Vec<(String, String)> = match &params {
+                    None => vec![],
+                    Some(Value::Object(fields)) => fields
+                        .iter()
+                        .map(|(k, v)| {
+                            (
+                                k.clone(),
+                                v.as_str().map(String::from).unwrap_or(v.to_string()),
+                            )
+                        })
+                        .collect(),
+                    Some(_) => {
+                        return Err(RawCommandError::InvalidParams(String::from(
+                            "IceRiver commands take an object of form fields",
+                        )));
+                    }
+                };
+                let form: Vec<(&str, &str)> = fields
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect();
+                self.web
+                    .send_command(command, &form)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
+    }
+}
+
+/// Parse an IceRiver hashrate, either a number with a separate unit prefix,
+/// or a string with the prefix appended such as `"10.5T"`
+fn parse_hashrate(value: &Value, unit: Option<&str>) -> Option<HashRate> {
+    let (value, unit) = match value {
+        Value::Number(n) => (n.as_f64()?, unit.unwrap_or("G")),
+        Value::String(s) => {
+            let s = s.trim().trim_end_matches(['H', 'h']);
+            match s.find(|c: char| c.is_ascii_alphabetic()) {
+                Some(idx) => (s[..idx].trim().parse().ok()?, &s[idx..]),
+                None => (s.parse().ok()?, unit.unwrap_or("G")),
+            }
+        }
+        _ => return None,
+    };
+    let unit = match unit.to_uppercase().as_str() {
+        "K" => HashRateUnit::KiloHash,
+        "M" => HashRateUnit::MegaHash,
+        "G" => HashRateUnit::GigaHash,
+        "T" => HashRateUnit::TeraHash,
+        "P" => HashRateUnit::PetaHash,
+        "" => HashRateUnit::Hash,
+        _ => return None,
+    };
+
+    Some(HashRate {
+        value,
+        unit,
+        algo: HashAlgorithm::KHeavyHash.to_string(),
+    })
+}
+
+/// Parse a runtime formatted as `days:hours:minutes:seconds`
+fn parse_runtime(runtime: &str) -> Option<Duration> {
+    let parts = runtime
+        .split(':')
+        .map(|p| p.trim().parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    let [days, hours, minutes, seconds] = parts[..] else {
+        return None;
+    };
+
+    Some(Duration::from_secs(
+        ((days * 24 + hours) * 60 + minutes) * 60 + seconds,
+    ))
+}
+
+fn parse_board(idx: usize, board: &Value, expected_chips: Option<u16>) -> BoardData {
+    let hashrate = parse_hashrate(&board["rtpow"], None);
+
+    BoardData {
+        position: board["no"]
+            .as_u64()
+            .map(|no| no.saturating_sub(1))
+            .unwrap_or(idx as u64) as u8,
+        active: Some(hashrate.as_ref().is_some_and(|hr| hr.value > 0.0)),
+        hashrate,
+        expected_hashrate: None,
+        board_temperature: None,
+        intake_temperature: board["intmp"].as_f64().map(Temperature::from_celsius),
+        outlet_temperature: board["outtmp"].as_f64().map(Temperature::from_celsius),
+        expected_chips,
+        working_chips: board["chipnum"].as_u64().map(|u| u as u16),
+        serial_number: None,
+        chips: vec![],
+        voltage: None,
+        frequency: board["freq"].as_f64().map(Frequency::from_megahertz),
+        tuned: None,
+    }
+}
+
+fn parse_pool((idx, pool): (usize, &Value)) -> PoolData {
+    PoolData {
+        position: Some(
+            pool["no"]
+                .as_u64()
+                .map(|no| no.saturating_sub(1))
+                .unwrap_or(idx as u64) as u16,
+        ),
+        url: pool["addr"].as_str().and_then(PoolURL::parse),
+        accepted_shares: pool["accepted"].as_u64(),
+        rejected_shares: pool["rejected"].as_u64(),
+        active: pool["connect"].as_bool(),
+        alive: pool["state"].as_i64().map(|state| state == 1),
+        user: pool["user"]
+            .as_str()
+            .filter(|u| !u.is_empty())
+            .map(String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_hashrate_and_runtime() {
+        let hashrate = parse_hashrate(&json!("10.52T"), None).unwrap();
+        assert_eq!(hashrate.value, 10.52);
+        assert_eq!(hashrate.unit, HashRateUnit::TeraHash);
+
+        let hashrate = parse_hashrate(&json!(512.5), Some("G")).unwrap();
+        assert_eq!(hashrate.unit, HashRateUnit::GigaHash);
+
+        let hashrate = parse_hashrate(&json!(512.5), Some("")).unwrap();
+        assert_eq!(hashrate.unit, HashRateUnit::Hash);
+
+        assert!(parse_hashrate(&json!("10.52X"), None).is_none());
+        assert!(parse_hashrate(&json!(512.5), Some("Z")).is_none());
+
+        assert_eq!(
+            parse_runtime("01:02:03:04"),
+            Some(Duration::from_secs(93784))
+        );
+        assert_eq!(parse_runtime("02:03:04"), None);
+    }
+}
@@ -0,0 +1,525 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use macaddr::MacAddr;
+use measurements::{AngularVelocity, Frequency, Power, Temperature, Voltage};
+use serde_json::{Value, json};
+
+use crate::data::board::BoardData;
+use crate::data::device::MinerMake::AntMiner;
+use crate::data::device::{DeviceInfo, HashAlgorithm, MinerFirmware, MinerHardware, MinerModel};
+use crate::data::fan::FanData;
+use crate::data::firmware::FirmwareVersion;
+use crate::data::hashrate::{HashRate, HashRateUnit};
+use crate::data::miner::MinerData;
+use crate::data::pool::{PoolData, PoolURL};
+use crate::miners::api::web::WebClient;
+use crate::miners::api::web::marathon_web_api::{MarathonError, MarathonWebApi};
+use crate::miners::backends::traits::{GetMinerData, RawCommandError, raw_web_method};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{
+    DataCollector, DataExtensions, DataExtractor, DataField, DataLocation, get_by_pointer,
+};
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::MinerTarget;
+
+/// Backend for AntMiners running Marathon (MARA) firmware
+pub struct Marathon {
+    model: MinerModel,
+    web: MarathonWebApi,
+    ip: IpAddr,
+    target: MinerTarget,
+}
+
+impl Marathon {
+    /// Create a backend for a target, connecting to a hostname directly by name
+    pub fn new(target: impl Into<MinerTarget>, model: MinerModel) -> Self {
+        let target = target.into();
+        let ports = target.ports(MinerPorts::new());
+        Marathon {
+            model,
+            web: MarathonWebApi::new(target.host().to_owned(), 80),
+            ip: target.data_ip(),
+            target,
+        }
+        .with_ports(ports)
+    }
+
+    /// Use non-standard ports or HTTPS for the web API
+    pub fn with_ports(mut self, ports: MinerPorts) -> Self {
+        self.web = MarathonWebApi::new(self.target.host().to_owned(), ports.web_port(80))
+            .with_https(ports.use_https);
+        self
+    }
+
+    /// Tune the miner to a power target in watts
+    pub async fn set_power_target(&self, watts: u32) -> Result<(), MarathonError> {
+        let mut config = self.web.miner_config().await?;
+        config["mode"]["work-mode-selector"] = json!("Auto");
+        config["mode"]["concorde"]["mode-select"] = json!("PowerTarget");
+        config["mode"]["concorde"]["power-target"] = json!(watts);
+        self.web.set_miner_config(config).await.map(|_| ())
+    }
+
+    /// Start or stop blinking the locate LED
+    pub async fn set_fault_light(&self, on: bool) -> Result<(), MarathonError> {
+        self.web.set_locate_miner(on).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl GetMinerData for Marathon {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut collector = DataCollector::new(self, &self.web);
+        let data = collector.collect(fields).await;
+
+        let miner_hardware = MinerHardware::from(&self.model);
+
+        let mac = data
+            .extract::<String>(DataField::Mac)
+            .and_then(|s| MacAddr::from_str(&s).ok());
+        let hostname = data.extract::<String>(DataField::Hostname);
+        let serial_number = data.extract::<String>(DataField::SerialNumber);
+        let firmware_version = data
+            .extract::<String>(DataField::FirmwareVersion)
+            .map(|v| FirmwareVersion::parse(MinerFirmware::Marathon, &v));
+        let control_board_version = data.extract::<String>(DataField::ControlBoardVersion);
+        let uptime = data.extract_map::<u64, _>(DataField::Uptime, Duration::from_secs);
+        let light_flashing = data.extract::<bool>(DataField::LightFlashing);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get system time")
+            .as_secs();
+
+        let hashrate = data.extract_map::<f64, _>(DataField::Hashrate, |f| HashRate {
+            value: f,
+            unit: HashRateUnit::TeraHash,
+            algo: String::from("SHA256"),
+        });
+        let wattage = data.extract_map::<f64, _>(DataField::Wattage, Power::from_watts);
+        let wattage_limit = data.extract_map::<f64, _>(DataField::WattageLimit, Power::from_watts);
+        let efficiency = match (&hashrate, wattage) {
+            (Some(hr), Some(w)) if hr.value > 0.0 => Some(w / hr.clone()),
+            _ => None,
+        };
+
+        let hashboards: Vec<BoardData> = data
+            .get(&DataField::Hashboards)
+            .and_then(|v| v.as_array())
+            .map(|boards| {
+                boards
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, board)| parse_board(idx, board, miner_hardware.chips))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let fans = data
+            .get(&DataField::Fans)
+            .and_then(|v| v.as_array())
+            .map(|fans| {
+                fans.iter()
+                    .enumerate()
+                    .filter_map(|(idx, fan)| {
+                        Some(FanData {
+                            position: fan["id"].as_i64().unwrap_or(idx as i64) as i16,
+                            rpm: AngularVelocity::from_rpm(fan["current_speed"].as_f64()?),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let average_temperature = {
+            let temps: Vec<f64> = hashboards
+                .iter()
+                .filter_map(|b| b.board_temperature)
+                .map(|t| t.as_celsius())
+                .collect();
+            if temps.is_empty() {
+                None
+            } else {
+                Some(Temperature::from_celsius(
+                    temps.iter().sum::<f64>() / temps.len() as f64,
+                ))
+            }
+        };
+
+        let pools = data
+            .get(&DataField::Pools)
+            .and_then(|v| v.as_array())
+            .map(|pools| pools.iter().enumerate().map(parse_pool).collect())
+            .unwrap_or_default();
+
+        let total_chips = hashboards
+            .iter()
+            .map(|b| b.working_chips)
+            .sum::<Option<u16>>();
+        let is_mining = data
+            .extract::<String>(DataField::IsMining)
+            .map(|status| status == "Mining")
+            .unwrap_or_else(|| hashrate.as_ref().is_some_and(|hr| hr.value > 0.0));
+
+        MinerData {
+            schema_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp,
+            ip: self.ip,
+            target_name: self.target.hostname().map(String::from),
+            mac,
+            device_info: DeviceInfo::new(
+                AntMiner,
+                self.model.clone(),
+                MinerFirmware::Marathon,
+                HashAlgorithm::SHA256,
+            ),
+            serial_number,
+            hostname,
+            api_version: None,
+            firmware_version,
+            control_board_version,
+            expected_hashboards: miner_hardware.boards,
+            hashboards,
+            hashrate,
+            expected_chips: match (miner_hardware.chips, miner_hardware.boards) {
+                (Some(chips), Some(boards)) => Some(chips * boards as u16),
+                _ => None,
+            },
+            total_chips,
+            expected_fans: miner_hardware.fans,
+            fans,
+            psu_fans: vec![],
+            average_temperature,
+            fluid_temperature: None,
+            wattage,
+            wattage_limit,
+            efficiency,
+            light_flashing,
+            messages: vec![],
+            uptime,
+            is_mining,
+            pools,
+            location: None,
+        }
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        const BRIEF_CMD: &str = "brief";
+        const OVERVIEW_CMD: &str = "overview";
+        const HASHBOARDS_CMD: &str = "hashboards";
+        const FANS_CMD: &str = "fans";
+        const POOLS_CMD: &str = "pools";
+        const NETWORK_CONFIG_CMD: &str = "network_config";
+        const MINER_CONFIG_CMD: &str = "miner_config";
+        const LOCATE_MINER_CMD: &str = "locate_miner";
+
+        match data_field {
+            DataField::Mac => &[(
+                NETWORK_CONFIG_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/mac"),
+                },
+            )],
+            DataField::Hostname => &[(
+                NETWORK_CONFIG_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/hostname"),
+                },
+            )],
+            DataField::SerialNumber => &[(
+                OVERVIEW_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/serial"),
+                },
+            )],
+            DataField::FirmwareVersion => &[(
+                OVERVIEW_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/version_firmware"),
+                },
+            )],
+            DataField::ControlBoardVersion => &[(
+                OVERVIEW_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/control_board"),
+                },
+            )],
+            DataField::Hashrate => &[(
+                BRIEF_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/hashrate_realtime"),
+                },
+            )],
+            DataField::Wattage => &[(
+                BRIEF_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/power_consumption_estimated"),
+                },
+            )],
+            DataField::Uptime => &[(
+                BRIEF_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/elapsed"),
+                },
+            )],
+            DataField::IsMining => &[(
+                BRIEF_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/status"),
+                },
+            )],
+            DataField::WattageLimit => &[(
+                MINER_CONFIG_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/mode/concorde/power-target"),
+                },
+            )],
+            DataField::Hashboards => &[(
+                HASHBOARDS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/hashboards"),
+                },
+            )],
+            DataField::Fans => &[(
+                FANS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/fans"),
+                },
+            )],
+            DataField::LightFlashing => &[(
+                LOCATE_MINER_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some("/blinking"),
+                },
+            )],
+            DataField::Pools => &[(
+                POOLS_CMD,
+                DataExtractor {
+                    func: get_by_pointer,
+                    key: Some(""),
+                },
+            )],
+            _ => &[],
+        }
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        match command {
+            MinerCommand::WebAPI { command } => {
+                let method = raw_web_method(&params);
+                self.web
+                    .send_command(command, params, method)
+                    .await
+                    .map_err(|e| RawCommandError::CommandFailed(e.to_string()))
+            }
+            _ => Err(RawCommandError::UnsupportedTransport(command)),
+        }
+    }
+}
+
+/// The highest reading of a list of temperature sensors
+fn max_temperature(value: &Value) -> Option<Temperature> {
+    value
+        .as_array()?
+        .iter()
+        .filter_map(|t| t.as_f64())
+        .reduce(f64::max)
+        .map(Temperature::from_celsius)
+}
+
+fn parse_board(idx: usize, board: &Value, expected_chips: Option<u16>) -> BoardData {
+    let hashrate = board["hashrate_average"].as_f64().map(|f| HashRate {
+        value: f,
+        unit: HashRateUnit::GigaHash,
+        algo: String::from("SHA256"),
+    });
+    let expected_hashrate = board["hashrate_ideal"].as_f64().map(|f| HashRate {
+        value: f,
+        unit: HashRateUnit::GigaHash,
+        algo: String::from("SHA256"),
+    });
+    BoardData {
+        position: board["index"].as_u64().unwrap_or(idx as u64) as u8,
+        active: Some(hashrate.as_ref().is_some_and(|hr| hr.value > 0.0)),
+        hashrate,
+        expected_hashrate,
+        board_temperature: max_temperature(&board["temperature_pcb"]),
+        // `temperature_chip` holds sensors on the chips, not readings at the intake or outlet
+        intake_temperature: None,
+        outlet_temperature: None,
+        expected_chips,
+        working_chips: board["asic_num"].as_u64().map(|u| u as u16),
+        serial_number: board["serial_number"].as_str().map(String::from),
+        chips: vec![],
+        voltage: board["voltage"].as_f64().map(Voltage::from_volts),
+        frequency: board["frequency"].as_f64().map(Frequency::from_megahertz),
+        tuned: None,
+    }
+}
+
+fn parse_pool((idx, pool): (usize, &Value)) -> PoolData {
+    PoolData {
+        position: Some(pool["index"].as_u64().unwrap_or(idx as u64) as u16),
+        url: pool["url"].as_str().and_then(PoolURL::parse),
+        accepted_shares: pool["accepted"].as_u64(),
+        rejected_shares: pool["rejected"].as_u64(),
+        active: pool["active"].as_bool(),
+        alive: pool["status"].as_str().map(|s| s == "Alive"),
+        user: pool["user"].as_str().map(String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::device::models::antminer::AntMinerModel;
+    use crate::miners::api::fixture::FixtureClient;
+
+    /// `/kaonsu/v1/` responses in the shape MARA firmware returns on an S21
+    fn client() -> FixtureClient {
+        FixtureClient::new([
+            (
+                "brief",
+                json!({
+                    "status": "Mining", "elapsed": 172800, "hashrate_realtime": 201.53,
+                    "hashrate_average": 200.12, "power_consumption_estimated": 3510.0
+                }),
+            ),
+            (
+                "overview",
+                json!({
+                    "model": "ANTMINER S21", "serial": "JYZZA1BBCJFAA0123",
+                    "version_firmware": "1.6.2", "control_board": "CVCtrl_V7"
+                }),
+            ),
+            (
+                "hashboards",
+                json!({
+                    "hashboards": [{
+                        "index": 0, "serial_number": "SMTTYAVBDJBAJ0456", "asic_num": 108,
+                        "hashrate_average": 66705.2, "hashrate_ideal": 67000.0,
+                        "temperature_pcb": [48, 51, 50, 53], "temperature_chip": [62, 70, 71, 66],
+                        "voltage": 13.2, "frequency": 490.0
+                    }]
+                }),
+            ),
+            (
+                "fans",
+                json!({
+                    "fans": [{"id": 0, "current_speed": 4080}, {"id": 1, "current_speed": 4110}]
+                }),
+            ),
+            (
+                "pools",
+                json!([{
+                    "index": 0, "url": "stratum+tcp://stratum.example.com:3333",
+                    "user": "worker.1", "status": "Alive", "active": true,
+                    "accepted": 4210, "rejected": 7
+                }]),
+            ),
+        ])
+    }
+
+    fn miner() -> Marathon {
+        Marathon::new(
+            IpAddr::from([10, 0, 0, 1]),
+            MinerModel::AntMiner(AntMinerModel::S21),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_brief_and_overview_locations() {
+        let miner = miner();
+        let client = client();
+        let mut collector = DataCollector::new(&miner, &client);
+
+        let data = collector
+            .collect(&[
+                DataField::Hashrate,
+                DataField::Wattage,
+                DataField::Uptime,
+                DataField::IsMining,
+                DataField::SerialNumber,
+                DataField::FirmwareVersion,
+                DataField::ControlBoardVersion,
+            ])
+            .await;
+
+        assert_eq!(data.extract::<f64>(DataField::Hashrate), Some(201.53));
+        assert_eq!(data.extract::<f64>(DataField::Wattage), Some(3510.0));
+        assert_eq!(data.extract::<u64>(DataField::Uptime), Some(172800));
+        assert_eq!(
+            data.extract::<String>(DataField::IsMining).as_deref(),
+            Some("Mining")
+        );
+        assert_eq!(
+            data.extract::<String>(DataField::SerialNumber).as_deref(),
+            Some("JYZZA1BBCJFAA0123")
+        );
+        assert_eq!(
+            data.extract::<String>(DataField::FirmwareVersion)
+                .as_deref(),
+            Some("1.6.2")
+        );
+        assert_eq!(
+            data.extract::<String>(DataField::ControlBoardVersion)
+                .as_deref(),
+            Some("CVCtrl_V7")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_hashboards_and_pools() {
+        let miner = miner();
+        let client = client();
+        let mut collector = DataCollector::new(&miner, &client);
+        let data = collector
+            .collect(&[DataField::Hashboards, DataField::Fans, DataField::Pools])
+            .await;
+
+        let boards = data[&DataField::Hashboards].as_array().unwrap();
+        let board = parse_board(0, &boards[0], Some(108));
+        assert_eq!(board.active, Some(true));
+        assert_eq!(board.hashrate.map(|hr| hr.value), Some(66705.2));
+        assert_eq!(board.expected_hashrate.map(|hr| hr.value), Some(67000.0));
+        assert_eq!(
+            board.board_temperature,
+            Some(Temperature::from_celsius(53.0))
+        );
+        assert_eq!(board.intake_temperature, None);
+        assert_eq!(board.outlet_temperature, None);
+        assert_eq!(board.working_chips, Some(108));
+        assert_eq!(board.serial_number.as_deref(), Some("SMTTYAVBDJBAJ0456"));
+
+        assert_eq!(data[&DataField::Fans].as_array().map(|f| f.len()), Some(2));
+
+        let pools = data[&DataField::Pools].as_array().unwrap();
+        let pool = parse_pool((0, &pools[0]));
+        assert!(pool.url.is_some());
+        assert_eq!(pool.accepted_shares, Some(4210));
+        assert_eq!(pool.rejected_shares, Some(7));
+        assert_eq!(pool.active, Some(true));
+        assert_eq!(pool.alive, Some(true));
+        assert_eq!(pool.user.as_deref(), Some("worker.1"));
+    }
+}
@@ -1,3 +1,9 @@
+pub mod auradine;
+pub mod braiins;
 pub mod btminer;
+pub mod cgminer;
 pub mod espminer;
+pub mod goldshell;
+pub mod iceriver;
+pub mod marathon;
 pub mod traits;
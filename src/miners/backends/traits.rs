@@ -1,17 +1,83 @@
 use crate::data::miner::MinerData;
+use crate::miners::backends::auradine::AuradineControl;
+use crate::miners::commands::MinerCommand;
 use crate::miners::data::{DataField, DataLocation};
 use async_trait::async_trait;
+use reqwest::Method;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use strum::IntoEnumIterator;
 
 /// Trait that every miner backend must implement to provide miner data.
 #[async_trait]
 pub trait GetMinerData: Send + Sync {
     /// Asynchronously retrieves standardized information about a miner,
     /// returning it as a `MinerData` struct.
-    async fn get_data(&self) -> MinerData;
+    async fn get_data(&self) -> MinerData {
+        self.get_data_fields(&DataField::iter().collect::<Vec<_>>())
+            .await
+    }
+
+    /// Retrieves only the given fields, sending the fewest commands needed for them.
+    ///
+    /// Fields that were not requested are left empty in the returned `MinerData`, apart
+    /// from those known without asking the miner, such as the IP and device info.
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData;
 
     /// Returns the locations of the specified data field on the miner.
     ///
     /// This associates API commands (routes) with `DataExtractor` structs,
     /// describing how to extract the data for a given `DataField`.
     fn get_locations(&self, data_field: DataField) -> &'static [DataLocation];
+
+    /// Send a command the library does not map, returning the raw response.
+    ///
+    /// The command is routed to the API client for its transport. Web commands are relative
+    /// to the client's API root, such as `system/info` on ESP-Miner, and are sent as a POST
+    /// when there are parameters.
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError>;
+
+    /// The Auradine specific controls, `None` if this miner is not an Auradine.
+    ///
+    /// Backends that wrap another miner return the controls of the miner they wrap.
+    fn as_auradine(&self) -> Option<&dyn AuradineControl> {
+        None
+    }
 }
+
+/// The HTTP method for a raw web command, a POST when there is a body to send
+pub(crate) fn raw_web_method(params: &Option<Value>) -> Method {
+    match params {
+        Some(_) => Method::POST,
+        None => Method::GET,
+    }
+}
+
+/// Error types for raw commands
+#[derive(Debug, Clone)]
+pub enum RawCommandError {
+    /// The miner has no API client for the command's transport
+    UnsupportedTransport(MinerCommand),
+    /// The parameters cannot be sent over the command's transport
+    InvalidParams(String),
+    /// The command was sent, but failed
+    CommandFailed(String),
+}
+
+impl Display for RawCommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawCommandError::UnsupportedTransport(command) => {
+                write!(f, "Unsupported transport for command: {:?}", command)
+            }
+            RawCommandError::InvalidParams(msg) => write!(f, "Invalid parameters: {}", msg),
+            RawCommandError::CommandFailed(msg) => write!(f, "Command failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RawCommandError {}
@@ -65,6 +65,20 @@ pub enum DataField {
     Pools,
 }
 
+impl DataField {
+    /// The fields this field can be computed from, for backends with no location for it.
+    pub fn sources(&self) -> &'static [DataField] {
+        match self {
+            DataField::Hashrate | DataField::TotalChips | DataField::AverageTemperature => {
+                &[DataField::Hashboards]
+            }
+            DataField::Efficiency => &[DataField::Hashrate, DataField::Wattage],
+            DataField::IsMining => &[DataField::Hashrate],
+            _ => &[],
+        }
+    }
+}
+
 /// A function pointer type that takes a JSON `Value` and an optional key,
 /// returning the extracted value if found.
 type ExtractorFn = for<'a> fn(&'a Value, Option<&'static str>) -> Option<&'a Value>;
@@ -272,27 +286,64 @@ impl<'a> DataCollector<'a> {
 
     /// Collects only the specified fields from the miner and returns a map of results.
     ///
-    /// This method sends only the minimum required set of API commands.
+    /// This method sends only the minimum required set of API commands. Fields the backend
+    /// has no location for also collect the fields they are computed from, so the results
+    /// may include fields that were not asked for.
     pub async fn collect(&mut self, fields: &[DataField]) -> HashMap<DataField, &Value> {
-        let mut results = HashMap::new();
-        let required_commands = self.get_required_commands(fields);
+        self.fetch(fields).await;
+        self.extract(fields)
+    }
+
+    /// Sends the commands needed for the specified fields and caches their responses.
+    ///
+    /// Use with [`extract`](Self::extract) and [`response`](Self::response) when a field
+    /// is built from several commands, and more than the first located value is needed.
+    pub async fn fetch(&mut self, fields: &[DataField]) {
+        let fields = self.with_sources(fields);
+        let required_commands = self.get_required_commands(&fields);
 
         for command in required_commands {
             if let Ok(response) = self.api_client.send_command(command).await {
                 self.cache.insert(command.to_string(), response);
             }
         }
+    }
 
-        // Extract the data for each field using the cached responses.
-        for &field in fields {
+    /// Extracts the specified fields, and the fields they are computed from, from the
+    /// responses cached by [`fetch`](Self::fetch).
+    pub fn extract(&self, fields: &[DataField]) -> HashMap<DataField, &Value> {
+        let mut results = HashMap::new();
+        for field in self.with_sources(fields) {
             if let Some(value) = self.extract_field(field) {
                 results.insert(field, value);
             }
         }
-
         results
     }
 
+    /// The cached response to a command, if it was sent and succeeded.
+    pub fn response(&self, command: &str) -> Option<&Value> {
+        self.cache.get(command)
+    }
+
+    /// Adds the sources of any field the backend has no location for, until every field
+    /// is either located or has no sources.
+    fn with_sources(&self, fields: &[DataField]) -> Vec<DataField> {
+        let mut fields = fields.to_vec();
+        let mut idx = 0;
+        while idx < fields.len() {
+            if self.miner.get_locations(fields[idx]).is_empty() {
+                for source in fields[idx].sources() {
+                    if !fields.contains(source) {
+                        fields.push(*source);
+                    }
+                }
+            }
+            idx += 1;
+        }
+        fields
+    }
+
     /// Determines the unique set of API commands needed for the requested fields.
     ///
     /// Uses the backend's location mappings to identify required commands.
@@ -318,3 +369,48 @@ impl<'a> DataCollector<'a> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::device::MinerModel;
+    use crate::data::device::models::goldshell::GoldshellModel;
+    use crate::miners::backends::goldshell::Goldshell;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::net::IpAddr;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingClient {
+        sent: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl ApiClient for RecordingClient {
+        async fn send_command(&self, command: &'static str) -> Result<Value, String> {
+            self.sent.lock().unwrap().push(command);
+            Ok(json!({"data": [{"hashrate": 100.0}]}))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_computed_field_from_sources() {
+        // Goldshell only reports hashrate per board, so it has no location for the total
+        let miner = Goldshell::new(
+            IpAddr::from([10, 0, 0, 1]),
+            MinerModel::Goldshell(GoldshellModel::KD5),
+        );
+        let client = RecordingClient::default();
+        let mut collector = DataCollector::new(&miner, &client);
+
+        let data = collector.collect(&[DataField::Hashrate]).await;
+
+        assert!(data.contains_key(&DataField::Hashboards));
+        assert!(!data.contains_key(&DataField::Pools));
+        assert_eq!(
+            *client.sent.lock().unwrap(),
+            vec!["cgminer?cgminercmd=devs"]
+        );
+    }
+}
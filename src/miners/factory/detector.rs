@@ -0,0 +1,177 @@
+use futures::future::BoxFuture;
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::Value;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use crate::miners::backends::traits::GetMinerData;
+use crate::miners::commands::MinerCommand;
+use crate::miners::ports::MinerPorts;
+use crate::miners::util::{send_rpc_command, send_web_command};
+
+/// The response to a discovery command, as handed to a [`MinerDetector`] matcher.
+#[derive(Debug, Clone)]
+pub enum DiscoveryResponse {
+    /// A successful response from the RPC API on port 4028
+    RPC(Value),
+    /// A response from the web server, including redirects and auth challenges
+    Web {
+        body: String,
+        headers: HeaderMap,
+        status: StatusCode,
+        /// Whether the response came over HTTPS
+        https: bool,
+    },
+}
+
+impl DiscoveryResponse {
+    /// Build a web response, such as one captured from a miner to test a matcher with.
+    ///
+    /// ```
+    /// use asic_rs::miners::factory::DiscoveryResponse;
+    ///
+    /// let response = DiscoveryResponse::web("<title>Prototype FW</title>", 200, &[]);
+    /// assert!(matches!(response, DiscoveryResponse::Web { body, .. } if body.contains("Prototype")));
+    /// ```
+    pub fn web(body: &str, status: u16, headers: &[(&str, &str)]) -> Self {
+        let headers = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect();
+        DiscoveryResponse::Web {
+            body: body.to_owned(),
+            headers,
+            status: StatusCode::from_u16(status).unwrap_or(StatusCode::OK),
+            https: false,
+        }
+    }
+
+    /// Send a discovery command to a miner, returning nothing if it could not be reached
+    /// or the command type is not supported for discovery.
+    ///
+    /// Web commands are also sent over HTTPS when HTTP is closed or redirects to HTTPS,
+    /// in which case both responses are returned.
+    pub async fn fetch(ip: IpAddr, ports: MinerPorts, command: &MinerCommand) -> Vec<Self> {
+        match command {
+            MinerCommand::RPC { command } => send_rpc_command(&ip, ports.rpc_port(), command)
+                .await
+                .map(DiscoveryResponse::RPC)
+                .into_iter()
+                .collect(),
+            MinerCommand::WebAPI { command } => {
+                let http = Self::fetch_web(ip, ports.http_port(), false, command).await;
+                let https = match http.as_ref().is_none_or(|r| r.redirects_to_https()) {
+                    true => Self::fetch_web(ip, ports.https_port(), true, command).await,
+                    false => None,
+                };
+                http.into_iter().chain(https).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    async fn fetch_web(ip: IpAddr, port: u16, https: bool, command: &'static str) -> Option<Self> {
+        send_web_command(&ip, port, https, command)
+            .await
+            .map(|(body, headers, status)| DiscoveryResponse::Web {
+                body,
+                headers,
+                status,
+                https,
+            })
+    }
+
+    pub(crate) fn redirects_to_https(&self) -> bool {
+        match self {
+            DiscoveryResponse::Web { headers, .. } => headers
+                .get("location")
+                .and_then(|l| l.to_str().ok())
+                .is_some_and(|l| l.starts_with("https://")),
+            _ => false,
+        }
+    }
+}
+
+type Matcher = dyn Fn(&DiscoveryResponse) -> bool + Send + Sync;
+type Constructor =
+    dyn Fn(IpAddr, MinerPorts) -> BoxFuture<'static, Option<Box<dyn GetMinerData>>> + Send + Sync;
+
+/// A user supplied detector for firmware asic-rs does not know about.
+///
+/// The factory sends the detector's discovery command alongside its own, and if the matcher
+/// accepts the response, the constructor is used to build the backend instead of a built in
+/// one, on the ports the miner was discovered on. Detectors are checked in the order they were registered, before the built in detection.
+///
+/// ```no_run
+/// use asic_rs::miners::commands::MinerCommand;
+/// use asic_rs::miners::factory::{DiscoveryResponse, MinerDetector, MinerFactory};
+///
+/// let detector = MinerDetector::new(
+///     "prototype",
+///     MinerCommand::WebAPI { command: "/" },
+///     |response| matches!(response, DiscoveryResponse::Web { body, .. } if body.contains("Prototype FW")),
+///     |_ip, _ports| Box::pin(async move { None }),
+/// );
+/// let mut factory = MinerFactory::new();
+/// factory.register_detector(detector);
+/// ```
+#[derive(Clone)]
+pub struct MinerDetector {
+    name: String,
+    command: MinerCommand,
+    matcher: Arc<Matcher>,
+    constructor: Arc<Constructor>,
+}
+
+impl MinerDetector {
+    pub fn new<M, C>(name: &str, command: MinerCommand, matcher: M, constructor: C) -> Self
+    where
+        M: Fn(&DiscoveryResponse) -> bool + Send + Sync + 'static,
+        C: Fn(IpAddr, MinerPorts) -> BoxFuture<'static, Option<Box<dyn GetMinerData>>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            name: name.to_owned(),
+            command,
+            matcher: Arc::new(matcher),
+            constructor: Arc::new(constructor),
+        }
+    }
+
+    /// The name this detector was registered under
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The discovery command this detector needs a response to
+    pub fn command(&self) -> &MinerCommand {
+        &self.command
+    }
+
+    /// Whether this detector recognizes a discovery response
+    pub fn matches(&self, response: &DiscoveryResponse) -> bool {
+        (self.matcher)(response)
+    }
+
+    /// Build the backend for a miner this detector recognized, on the ports it was found on
+    pub async fn construct(&self, ip: IpAddr, ports: MinerPorts) -> Option<Box<dyn GetMinerData>> {
+        (self.constructor)(ip, ports).await
+    }
+}
+
+impl std::fmt::Debug for MinerDetector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MinerDetector")
+            .field("name", &self.name)
+            .field("command", &self.command)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,44 +1,133 @@
 mod commands;
+mod detector;
 mod hardware;
 mod model;
+mod report;
 mod traits;
 
-use futures::future::FutureExt;
-use futures::pin_mut;
 use reqwest::StatusCode;
 use reqwest::header::HeaderMap;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
 use std::net::IpAddr;
+use std::sync::Arc;
 use std::time::Duration;
-use std::{collections::HashSet, error::Error};
 use tokio::task::JoinSet;
 
 use super::commands::MinerCommand;
-use super::util::{send_rpc_command, send_web_command};
 use crate::data::device::{MinerFirmware, MinerMake, MinerModel};
+use crate::miners::backends::auradine::Auradine;
+use crate::miners::backends::braiins::BraiinsMini;
 use crate::miners::backends::btminer::BTMinerV3Backend;
+use crate::miners::backends::cgminer::CGMiner;
 use crate::miners::backends::espminer::ESPMiner;
+use crate::miners::backends::goldshell::Goldshell;
+use crate::miners::backends::iceriver::IceRiver;
+use crate::miners::backends::marathon::Marathon;
 use crate::miners::backends::traits::GetMinerData;
+use crate::miners::ports::MinerPorts;
+use crate::miners::target::{MinerResolver, MinerTarget, NamedMiner, SystemResolver, TargetError};
+pub use detector::{DiscoveryResponse, MinerDetector};
+pub use hardware::{
+    HardwareError, apply_hardware_overrides, load_hardware_overrides, reset_hardware_overrides,
+};
+pub use report::{DiscoveryFailure, DiscoveryProtocol, DiscoveryReport};
 use traits::{DiscoveryCommands, ModelSelection};
 
 const MAX_WAIT_TIME: Duration = Duration::from_secs(5);
 
-async fn get_miner_type_from_command(
-    ip: IpAddr,
-    command: MinerCommand,
-) -> Option<(Option<MinerMake>, Option<MinerFirmware>)> {
-    match command {
-        MinerCommand::RPC { command } => {
-            let response = send_rpc_command(&ip, command).await?;
-            parse_type_from_socket(response)
-        }
-        MinerCommand::WebAPI { command } => {
-            let response = send_web_command(&ip, command).await?;
-            parse_type_from_web(response)
+/// Discovery responses keyed by the command that was sent, reused when reading the model
+pub(crate) type DiscoveryResponses = HashMap<MinerCommand, Vec<DiscoveryResponse>>;
+
+/// What a discovery command identified a miner as
+enum DiscoveryMatch {
+    /// A make and firmware handled by the built in backends
+    BuiltIn(Option<MinerMake>, Option<MinerFirmware>),
+    /// A user registered detector
+    Custom(MinerDetector),
+}
+
+impl DiscoveryMatch {
+    /// How specific this match is, the highest is used when responses disagree.
+    ///
+    /// Custom firmware often leaves stock pages in place, such as the AntMiner login on
+    /// BraiinsOS, so a firmware match beats a stock make.
+    fn precedence(&self) -> u8 {
+        match self {
+            DiscoveryMatch::Custom(_) => 3,
+            DiscoveryMatch::BuiltIn(_, Some(firmware)) if *firmware != MinerFirmware::Stock => 2,
+            DiscoveryMatch::BuiltIn(Some(_), _) => 1,
+            DiscoveryMatch::BuiltIn(None, _) => 0,
         }
-        _ => None,
     }
 }
 
+/// Identify a miner from a single discovery response
+fn match_response(
+    command: &MinerCommand,
+    response: &DiscoveryResponse,
+    detectors: &[MinerDetector],
+) -> Option<DiscoveryMatch> {
+    // user detectors take priority, as prototype firmware often reuses stock strings
+    if let Some(detector) = detectors
+        .iter()
+        .find(|d| d.command() == command && d.matches(response))
+    {
+        return Some(DiscoveryMatch::Custom(detector.clone()));
+    }
+
+    let (make, firmware) = match response.clone() {
+        DiscoveryResponse::RPC(response) => parse_type_from_socket(response),
+        DiscoveryResponse::Web {
+            body,
+            headers,
+            status,
+            ..
+        } => parse_type_from_web((body, headers, status)),
+    }?;
+    Some(DiscoveryMatch::BuiltIn(make, firmware))
+}
+
+/// Identify a miner from all of its discovery responses, resolving conflicts by precedence
+fn identify_miner(
+    responses: &DiscoveryResponses,
+    detectors: &[MinerDetector],
+) -> Option<DiscoveryMatch> {
+    let mut matches: Vec<(&MinerCommand, usize, DiscoveryMatch)> = responses
+        .iter()
+        .flat_map(|(command, responses)| {
+            // a redirect to HTTPS only hints at a WhatsMiner, so once the HTTPS page itself
+            // was read the redirect is ignored in favour of what that page says
+            let has_https = responses
+                .iter()
+                .any(|r| matches!(r, DiscoveryResponse::Web { https: true, .. }));
+            responses
+                .iter()
+                .enumerate()
+                .filter(move |(_, response)| !(has_https && response.redirects_to_https()))
+                .filter_map(move |(idx, response)| {
+                    Some((command, idx, match_response(command, response, detectors)?))
+                })
+        })
+        .collect();
+
+    // RPC responses name the miner software directly, so they break ties with web pages,
+    // and the command itself keeps the result independent of response order
+    matches.sort_by_key(|(command, idx, miner_match)| {
+        (
+            Reverse(miner_match.precedence()),
+            !matches!(command, MinerCommand::RPC { .. }),
+            format!("{:?}", command),
+            *idx,
+        )
+    });
+    matches
+        .into_iter()
+        .next()
+        .map(|(_, _, miner_match)| miner_match)
+}
+
 fn parse_type_from_socket(
     response: serde_json::Value,
 ) -> Option<(Option<MinerMake>, Option<MinerFirmware>)> {
@@ -52,6 +141,22 @@ fn parse_type_from_socket(
         _ if json_string.contains("BITMICRO") || json_string.contains("BTMINER") => {
             Some((Some(MinerMake::WhatsMiner), Some(MinerFirmware::Stock)))
         }
+        // clones and third party firmware often copy AntMiner strings, so check for them first
+        _ if json_string.contains("HIVEON") => {
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::HiveOS)))
+        }
+        _ if json_string.contains("MSKMINER") => {
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::MSKMiner)))
+        }
+        _ if json_string.contains("ELPHAPEX") => {
+            Some((Some(MinerMake::Elphapex), Some(MinerFirmware::Stock)))
+        }
+        _ if json_string.contains("VOLCMINER") => {
+            Some((Some(MinerMake::Volcminer), Some(MinerFirmware::Stock)))
+        }
+        _ if json_string.contains("HAMMER") => {
+            Some((Some(MinerMake::Hammer), Some(MinerFirmware::Stock)))
+        }
         _ if json_string.contains("ANTMINER") && !json_string.contains("DEVDETAILS") => {
             Some((Some(MinerMake::AntMiner), Some(MinerFirmware::Stock)))
         }
@@ -81,6 +186,15 @@ fn parse_type_from_web(
             Some((Some(MinerMake::AntMiner), Some(MinerFirmware::Stock)))
         }
         _ if resp_text.contains("Braiins OS") => Some((None, Some(MinerFirmware::BraiinsOS))),
+        _ if resp_text.contains("MARA FW") || resp_text.contains("Marathon") => {
+            Some((None, Some(MinerFirmware::Marathon)))
+        }
+        _ if resp_text.contains("Hiveon") || resp_text.contains("hiveon") => {
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::HiveOS)))
+        }
+        _ if resp_text.contains("MSKMiner") => {
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::MSKMiner)))
+        }
         _ if resp_text.contains("Luxor Firmware") => Some((None, Some(MinerFirmware::LuxOS))),
         _ if resp_text.contains("AxeOS") => {
             Some((Some(MinerMake::BitAxe), Some(MinerFirmware::Stock)))
@@ -89,6 +203,15 @@ fn parse_type_from_web(
         _ if resp_text.contains("Avalon") => {
             Some((Some(MinerMake::AvalonMiner), Some(MinerFirmware::Stock)))
         }
+        _ if resp_text.contains("Goldshell") => {
+            Some((Some(MinerMake::Goldshell), Some(MinerFirmware::Stock)))
+        }
+        _ if resp_text.contains("IceRiver") || resp_text.contains("ICERIVER") => {
+            Some((Some(MinerMake::IceRiver), Some(MinerFirmware::Stock)))
+        }
+        _ if resp_text.contains("Auradine") => {
+            Some((Some(MinerMake::Auradine), Some(MinerFirmware::Stock)))
+        }
         _ if resp_text.contains("AnthillOS") => Some((None, Some(MinerFirmware::VNish))),
         _ if redirect_header.contains("https://") && resp_status == 307
             || resp_text.contains("/cgi-bin/luci") =>
@@ -100,30 +223,123 @@ fn parse_type_from_web(
 }
 fn select_backend(
     ip: IpAddr,
+    ports: MinerPorts,
     make: Option<MinerMake>,
     model: Option<MinerModel>,
     firmware: Option<MinerFirmware>,
 ) -> Option<Box<dyn GetMinerData>> {
     match (make, firmware) {
         (Some(MinerMake::WhatsMiner), Some(MinerFirmware::Stock)) => Some(Box::new(
-            BTMinerV3Backend::new(ip, model.expect("Could not find model")),
+            BTMinerV3Backend::new(ip, model?).with_ports(ports),
+        )),
+        (_, Some(MinerFirmware::BraiinsOS))
+            if matches!(
+                model,
+                Some(MinerModel::Braiins(_))
+                    | Some(MinerModel::Unknown {
+                        make: MinerMake::Braiins,
+                        ..
+                    })
+            ) =>
+        {
+            Some(Box::new(BraiinsMini::new(ip, model?).with_ports(ports)))
+        }
+        (_, Some(MinerFirmware::Marathon)) => {
+            Some(Box::new(Marathon::new(ip, model?).with_ports(ports)))
+        }
+        (_, Some(firmware)) if CGMiner::adapter_for_firmware(firmware).is_some() => Some(Box::new(
+            CGMiner::new(ip, model?, CGMiner::adapter_for_firmware(firmware)?).with_ports(ports),
         )),
-        (Some(MinerMake::BitAxe), Some(MinerFirmware::Stock)) => {
-            Some(Box::new(ESPMiner::new(ip, model?, firmware?)))
+        (Some(MinerMake::BitAxe), Some(MinerFirmware::Stock)) => Some(Box::new(
+            ESPMiner::new(ip, model?, firmware?).with_ports(ports),
+        )),
+        (Some(MinerMake::Goldshell), Some(MinerFirmware::Stock)) => {
+            Some(Box::new(Goldshell::new(ip, model?).with_ports(ports)))
+        }
+        (Some(MinerMake::IceRiver), Some(MinerFirmware::Stock)) => {
+            Some(Box::new(IceRiver::new(ip, model?).with_ports(ports)))
         }
+        (Some(MinerMake::Auradine), Some(MinerFirmware::Stock)) => {
+            Some(Box::new(Auradine::new(ip, model?).with_ports(ports)))
+        }
+        (Some(make), Some(MinerFirmware::Stock)) => Some(Box::new(
+            CGMiner::new(ip, model?, CGMiner::adapter_for(make)).with_ports(ports),
+        )),
         _ => None,
     }
 }
 
+#[derive(Clone)]
 pub struct MinerFactory {
     search_makes: Option<Vec<MinerMake>>,
     search_firmwares: Option<Vec<MinerFirmware>>,
+    detectors: Vec<MinerDetector>,
+    ports: MinerPorts,
+    resolver: Arc<dyn MinerResolver>,
 }
 impl MinerFactory {
+    /// Get a miner by IP address, hostname, or either with a web port
     pub async fn get_miner(
-        self,
-        ip: IpAddr,
+        &self,
+        target: impl TryInto<MinerTarget, Error: Into<TargetError>>,
     ) -> Result<Option<Box<dyn GetMinerData>>, Box<dyn Error>> {
+        let (miner, _) = self.discover(target).await?;
+        Ok(miner)
+    }
+
+    /// Get a miner on non-standard ports, such as one behind port forwarding
+    pub async fn get_miner_with_ports(
+        &self,
+        target: impl TryInto<MinerTarget, Error: Into<TargetError>>,
+        ports: MinerPorts,
+    ) -> Result<Option<Box<dyn GetMinerData>>, Box<dyn Error>> {
+        let (miner, _) = self.discover_with_ports(target, ports).await?;
+        Ok(miner)
+    }
+
+    /// Discover a miner, returning its backend along with a report of what was identified.
+    ///
+    /// The report explains why no backend was returned, such as an unsupported model.
+    /// Fails only if the target is a hostname that could not be resolved.
+    pub async fn discover(
+        &self,
+        target: impl TryInto<MinerTarget, Error: Into<TargetError>>,
+    ) -> Result<(Option<Box<dyn GetMinerData>>, DiscoveryReport), TargetError> {
+        self.discover_with_ports(target, self.ports).await
+    }
+
+    /// Discover a miner on non-standard ports, see [`MinerFactory::discover`]
+    pub async fn discover_with_ports(
+        &self,
+        target: impl TryInto<MinerTarget, Error: Into<TargetError>>,
+        ports: MinerPorts,
+    ) -> Result<(Option<Box<dyn GetMinerData>>, DiscoveryReport), TargetError> {
+        let target = target.try_into().map_err(Into::into)?;
+        let ip = self.resolve(&target).await?;
+        let (miner, mut report) = self.discover_ip(ip, target.ports(ports)).await;
+
+        // miners found by name carry it, so their data can be matched back to the inventory
+        let Some(name) = target.hostname() else {
+            return Ok((miner, report));
+        };
+        report.target_name = Some(name.to_owned());
+        let miner = miner.map(|m| Box::new(NamedMiner::new(name, m)) as Box<dyn GetMinerData>);
+        Ok((miner, report))
+    }
+
+    /// Resolve a target to the IP address of the miner
+    pub async fn resolve(&self, target: &MinerTarget) -> Result<IpAddr, TargetError> {
+        match target.ip() {
+            Some(ip) => Ok(ip),
+            None => self.resolver.resolve(target.host()).await,
+        }
+    }
+
+    async fn discover_ip(
+        &self,
+        ip: IpAddr,
+        ports: MinerPorts,
+    ) -> (Option<Box<dyn GetMinerData>>, DiscoveryReport) {
         let search_makes = self.search_makes.clone().unwrap_or(vec![
             MinerMake::AntMiner,
             MinerMake::WhatsMiner,
@@ -131,6 +347,12 @@ impl MinerFactory {
             MinerMake::EPic,
             MinerMake::Braiins,
             MinerMake::BitAxe,
+            MinerMake::Goldshell,
+            MinerMake::IceRiver,
+            MinerMake::Auradine,
+            MinerMake::Elphapex,
+            MinerMake::Volcminer,
+            MinerMake::Hammer,
         ]);
         let search_firmwares = self.search_firmwares.clone().unwrap_or(vec![
             MinerFirmware::Stock,
@@ -154,60 +376,116 @@ impl MinerFactory {
                 commands.insert(command);
             }
         }
+        for detector in &self.detectors {
+            commands.insert(detector.command().clone());
+        }
 
         let mut discovery_tasks = JoinSet::new();
         for command in commands {
-            let _ = discovery_tasks.spawn(get_miner_type_from_command(ip, command));
-        }
-
-        let timeout = tokio::time::sleep(MAX_WAIT_TIME).fuse();
-        let tasks = tokio::spawn(async move {
-            loop {
-                if discovery_tasks.is_empty() {
-                    return None;
-                };
-                match discovery_tasks.join_next().await.unwrap_or(Ok(None)) {
-                    Ok(Some(result)) => {
-                        return Some(result);
-                    }
-                    _ => continue,
-                };
+            let _ = discovery_tasks.spawn(async move {
+                let responses = DiscoveryResponse::fetch(ip, ports, &command).await;
+                (command, responses)
+            });
+        }
+
+        // wait for every response rather than the first match, so the result does not
+        // depend on which command happened to answer first
+        let mut responses = DiscoveryResponses::new();
+        let deadline = tokio::time::Instant::now() + MAX_WAIT_TIME;
+        while let Ok(Some(result)) =
+            tokio::time::timeout_at(deadline, discovery_tasks.join_next()).await
+        {
+            if let Ok((command, command_responses)) = result {
+                if !command_responses.is_empty() {
+                    responses.insert(command, command_responses);
+                }
             }
-        });
+        }
 
-        pin_mut!(timeout, tasks);
+        let mut report = DiscoveryReport::new(ip);
+        let mut protocols: Vec<DiscoveryProtocol> = responses
+            .values()
+            .flatten()
+            .map(DiscoveryProtocol::from_response)
+            .collect();
+        protocols.sort();
+        protocols.dedup();
+        report.protocols = protocols;
 
-        let miner_info = tokio::select!(
-            Ok(miner_info) = &mut tasks => {
-                miner_info
-            },
-            _ = &mut timeout => {
-                None
-            }
-        );
+        // HTTPS is only probed when HTTP is closed or redirects to it, so the backend needs it too
+        let ports = MinerPorts {
+            use_https: ports.use_https || report.protocols.contains(&DiscoveryProtocol::HTTPS),
+            ..ports
+        };
 
-        match miner_info {
-            Some((make, firmware)) => {
-                let model = if let Some(miner_make) = make {
-                    miner_make.get_model(ip).await
-                } else if let Some(miner_firmware) = firmware {
-                    miner_firmware.get_model(ip).await
-                } else {
-                    return Ok(None);
-                };
-                Ok(select_backend(ip, make, model, firmware))
+        let (make, firmware) = match identify_miner(&responses, &self.detectors) {
+            Some(DiscoveryMatch::Custom(detector)) => {
+                report.detector = Some(detector.name().to_owned());
+                let miner = detector.construct(ip, ports).await;
+                report.failure = report.classify(miner.is_some());
+                return (miner, report);
             }
-            None => Ok(None),
+            Some(DiscoveryMatch::BuiltIn(make, firmware)) => (make, firmware),
+            None => {
+                report.failure = report.classify(false);
+                return (None, report);
+            }
+        };
+        report.make = make;
+        report.firmware = firmware;
+
+        let model = match (make, firmware) {
+            (Some(miner_make), _) => miner_make.get_model(ip, ports, &responses).await,
+            (None, Some(miner_firmware)) => miner_firmware.get_model(ip, ports, &responses).await,
+            (None, None) => None,
+        };
+        if let Some(MinerModel::Unknown { raw, .. }) = &model {
+            report.raw_model = Some(raw.clone());
         }
+        report.model = model.clone();
+
+        let miner = select_backend(ip, ports, make, model, firmware);
+        report.failure = report.classify(miner.is_some());
+        (miner, report)
     }
 
     pub fn new() -> MinerFactory {
         MinerFactory {
             search_makes: None,
             search_firmwares: None,
+            detectors: vec![],
+            ports: MinerPorts::default(),
+            resolver: Arc::new(SystemResolver),
         }
     }
 
+    /// Register a detector and backend for firmware that is not supported out of the box
+    pub fn register_detector(&mut self, detector: MinerDetector) -> &Self {
+        self.detectors.push(detector);
+        self
+    }
+    /// Remove all detectors registered under a name
+    pub fn unregister_detector(&mut self, name: &str) -> &Self {
+        self.detectors.retain(|d| d.name() != name);
+        self
+    }
+    /// The registered custom detectors, in the order they are checked
+    pub fn detectors(&self) -> &[MinerDetector] {
+        &self.detectors
+    }
+
+    /// Set the ports used to reach every miner, for fleets on non-standard ports
+    pub fn with_ports(&mut self, ports: MinerPorts) -> &Self {
+        self.ports = ports;
+        self
+    }
+
+    /// Set the resolver used for targets given by hostname
+    pub fn with_resolver(&mut self, resolver: impl MinerResolver + 'static) -> &Self {
+        self.resolver = Arc::new(resolver);
+        self
+    }
+
     pub fn with_search_makes(&mut self, search_makes: Vec<MinerMake>) -> &Self {
         self.search_makes = Some(search_makes);
         self
@@ -257,6 +535,7 @@ impl MinerFactory {
 
 #[cfg(test)]
 mod tests {
+    use super::commands::{HTTP_WEB_ROOT, RPC_VERSION};
     use super::*;
 
     #[test]
@@ -282,4 +561,140 @@ mod tests {
             Some((Some(MinerMake::WhatsMiner), Some(MinerFirmware::Stock)))
         )
     }
+    #[test]
+    fn test_parse_type_mskminer() {
+        let version = serde_json::json!({"VERSION": [
+            {"BMMiner": "2.0.0", "API": "3.1", "Miner": "MSKMiner 1.3.2", "Type": "Antminer S19"}
+        ]});
+        assert_eq!(
+            parse_type_from_socket(version),
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::MSKMiner)))
+        );
+
+        let body = String::from("<html><head><title>MSKMiner</title></head></html>");
+        assert_eq!(
+            parse_type_from_web((body, HeaderMap::new(), StatusCode::OK)),
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::MSKMiner)))
+        );
+    }
+
+    #[test]
+    fn test_parse_type_hiveos() {
+        let version = serde_json::json!({"VERSION": [
+            {"BMMiner": "1.0.0", "API": "3.1", "Miner": "Hiveon 1.02@210419", "Type": "Antminer S9"}
+        ]});
+        assert_eq!(
+            parse_type_from_socket(version),
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::HiveOS)))
+        );
+
+        let body = String::from("<html><head><title>hiveon asic</title></head></html>");
+        assert_eq!(
+            parse_type_from_web((body, HeaderMap::new(), StatusCode::OK)),
+            Some((Some(MinerMake::AntMiner), Some(MinerFirmware::HiveOS)))
+        );
+    }
+
+    #[test]
+    fn test_identify_miner_prefers_custom_firmware() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "www-authenticate",
+            "Digest realm=\"antMiner Configuration\"".parse().unwrap(),
+        );
+        let mut responses = DiscoveryResponses::new();
+        responses.insert(
+            HTTP_WEB_ROOT,
+            vec![DiscoveryResponse::Web {
+                body: String::new(),
+                headers,
+                status: StatusCode::UNAUTHORIZED,
+                https: false,
+            }],
+        );
+        responses.insert(
+            RPC_VERSION,
+            vec![DiscoveryResponse::RPC(
+                serde_json::json!({"VERSION": [{"BOSminer": "0.2.0"}]}),
+            )],
+        );
+
+        let result = identify_miner(&responses, &[]);
+        assert!(matches!(
+            result,
+            Some(DiscoveryMatch::BuiltIn(
+                None,
+                Some(MinerFirmware::BraiinsOS)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_identify_miner_ignores_https_redirect_with_https_page() {
+        let mut responses = DiscoveryResponses::new();
+        responses.insert(
+            HTTP_WEB_ROOT,
+            vec![
+                DiscoveryResponse::web("", 307, &[("location", "https://10.0.0.5/")]),
+                DiscoveryResponse::Web {
+                    body: String::from("<html><title>Goldshell Miner</title></html>"),
+                    headers: HeaderMap::new(),
+                    status: StatusCode::OK,
+                    https: true,
+                },
+            ],
+        );
+
+        let result = identify_miner(&responses, &[]);
+        assert!(matches!(
+            result,
+            Some(DiscoveryMatch::BuiltIn(
+                Some(MinerMake::Goldshell),
+                Some(MinerFirmware::Stock)
+            ))
+        ));
+
+        // without the HTTPS page the redirect still identifies a WhatsMiner
+        responses.get_mut(&HTTP_WEB_ROOT).unwrap().pop();
+        let result = identify_miner(&responses, &[]);
+        assert!(matches!(
+            result,
+            Some(DiscoveryMatch::BuiltIn(
+                Some(MinerMake::WhatsMiner),
+                Some(MinerFirmware::Stock)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_registered_detector_wins_over_built_in() {
+        let mut responses = DiscoveryResponses::new();
+        responses.insert(
+            RPC_VERSION,
+            vec![DiscoveryResponse::RPC(serde_json::json!(
+                {"VERSION": [{"CGMiner": "4.9.0", "Type": "Antminer S19"}]}
+            ))],
+        );
+        let detector = MinerDetector::new(
+            "prototype",
+            RPC_VERSION,
+            |response| matches!(response, DiscoveryResponse::RPC(v) if v.to_string().contains("S19")),
+            |_ip, _ports| Box::pin(async move { None }),
+        );
+
+        let mut factory = MinerFactory::new();
+        factory.register_detector(detector);
+        let result = identify_miner(&responses, factory.detectors());
+        assert!(matches!(result, Some(DiscoveryMatch::Custom(d)) if d.name() == "prototype"));
+
+        factory.unregister_detector("prototype");
+        let result = identify_miner(&responses, factory.detectors());
+        assert!(matches!(
+            result,
+            Some(DiscoveryMatch::BuiltIn(
+                Some(MinerMake::AntMiner),
+                Some(MinerFirmware::Stock)
+            ))
+        ));
+    }
 }
@@ -1,123 +1,439 @@
 use crate::data::device::models::MinerModelFactory;
-use crate::data::device::models::bitaxe::BitaxeModel::*;
+use crate::data::device::models::bitaxe::BitaxeModel::{self, *};
 use crate::data::device::{MinerFirmware, MinerMake, MinerModel};
+use crate::data::firmware::FirmwareVersion;
+use crate::miners::api::web::WebClient;
+use crate::miners::api::web::goldshell_web_api::GoldshellWebApi;
+use crate::miners::api::web::iceriver_web_api::IceRiverWebApi;
+use crate::miners::api::web::marathon_web_api::MarathonWebApi;
+use crate::miners::backends::auradine::Auradine;
+use crate::miners::commands::MinerCommand;
 use crate::miners::factory::model::whatsminer::{get_model_whatsminer_v2, get_model_whatsminer_v3};
+use crate::miners::factory::{DiscoveryResponse, DiscoveryResponses};
+use crate::miners::ports::MinerPorts;
 use crate::miners::util;
 use diqwest::WithDigestAuth;
 use reqwest::{Client, Response};
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 
 pub mod whatsminer;
 
-pub(crate) async fn get_model_antminer(ip: IpAddr) -> Option<MinerModel> {
-    let response: Option<Response> = Client::new()
-        .get(format!("http://{}/cgi-bin/get_system_info.cgi", ip))
+/// The response to an RPC command, if it was already sent during discovery
+fn cached_rpc_response<'a>(
+    responses: &'a DiscoveryResponses,
+    command: &'static str,
+) -> Option<&'a serde_json::Value> {
+    match responses.get(&MinerCommand::RPC { command })?.first()? {
+        DiscoveryResponse::RPC(response) => Some(response),
+        _ => None,
+    }
+}
+
+/// Send an RPC command, reusing the response if the command was already sent during discovery
+async fn send_rpc_command(
+    ip: &IpAddr,
+    ports: MinerPorts,
+    command: &'static str,
+    responses: &DiscoveryResponses,
+) -> Option<serde_json::Value> {
+    match cached_rpc_response(responses, command) {
+        Some(response) => Some(response.clone()),
+        None => util::send_rpc_command(ip, ports.rpc_port(), command).await,
+    }
+}
+
+/// Normalize an AntMiner type, such as `Antminer S19j Pro (Hiveon)`, to a model name
+fn antminer_model_name(raw: &str) -> String {
+    let model = raw.to_uppercase();
+    // the type can carry a firmware suffix
+    let model = model.split(" (").next().unwrap_or_default().trim();
+    match model.starts_with("ANTMINER ") {
+        true => model.to_string(),
+        false => format!("ANTMINER {}", model),
+    }
+}
+
+pub(crate) async fn get_model_antminer(
+    ip: IpAddr,
+    ports: MinerPorts,
+    responses: &DiscoveryResponses,
+) -> Option<MinerModel> {
+    const SYSTEM_INFO_PAGE: &str = "/cgi-bin/get_system_info.cgi";
+
+    // the version response from discovery names the model, without needing to log in
+    let from_version = cached_rpc_response(responses, "version")
+        .and_then(|version| version["VERSION"][0]["Type"].as_str())
+        .and_then(|model| {
+            MinerModelFactory::new()
+                .with_make(MinerMake::AntMiner)
+                .parse_model(&antminer_model_name(model), model)
+        });
+    if from_version.is_some() {
+        return from_version;
+    }
+
+    let scheme = match ports.use_https {
+        true => "https",
+        false => "http",
+    };
+    let client = Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .ok()?;
+    let response: Option<Response> = client
+        .get(format!(
+            "{}://{}{}",
+            scheme,
+            SocketAddr::new(ip, ports.web_port(80)),
+            SYSTEM_INFO_PAGE
+        ))
         .send_with_digest_auth("root", "root")
         .await
         .ok();
     match response {
         Some(data) => {
             let json_data = data.json::<serde_json::Value>().await.ok()?;
-            let model = json_data["minertype"].as_str().unwrap_or("").to_uppercase();
+            let raw = json_data["minertype"].as_str().unwrap_or("");
+            let model = raw.to_uppercase();
 
             MinerModelFactory::new()
                 .with_make(MinerMake::AntMiner)
-                .parse_model(&model)
+                .parse_model(&model, raw)
         }
         None => None,
     }
 }
 
-pub(crate) async fn get_model_whatsminer(ip: IpAddr) -> Option<MinerModel> {
-    let response = util::send_rpc_command(&ip, "get_version").await;
-
-    match response {
-        Some(json_data) => {
-            let fw_version: Option<&str> = json_data["Msg"]["fw_ver"].as_str();
-            if fw_version.is_none() {
-                return None;
-            }
-
-            let fw_version = fw_version.unwrap();
+pub(crate) async fn get_model_whatsminer(
+    ip: IpAddr,
+    ports: MinerPorts,
+    responses: &DiscoveryResponses,
+) -> Option<MinerModel> {
+    // firmware that still has the v2 API answers devdetails during discovery, with the model
+    let from_devdetails = cached_rpc_response(responses, "devdetails")
+        .and_then(|devdetails| devdetails["DEVDETAILS"][0]["Model"].as_str())
+        .and_then(whatsminer::parse_model);
+    if from_devdetails.is_some() {
+        return from_devdetails;
+    }
 
-            // Parse the firmware version format: YYYYMMDD.XX.REL
-            // Extract the date components
-            if fw_version.len() < 8 {
-                return None;
-            }
+    let response = send_rpc_command(&ip, ports, "get_version", responses).await?;
+    let fw_version =
+        FirmwareVersion::parse(MinerFirmware::Stock, response["Msg"]["fw_ver"].as_str()?);
 
-            let date_part = &fw_version[..8];
-            if let (Ok(year), Ok(month), Ok(_day)) = (
-                date_part[..4].parse::<u32>(),
-                date_part[4..6].parse::<u32>(),
-                date_part[6..8].parse::<u32>(),
-            ) {
-                // Determine which API version to use based on the firmware date
-                if year >= 2025 || (year == 2024 && month >= 11) {
-                    get_model_whatsminer_v3(ip).await
-                } else {
-                    get_model_whatsminer_v2(ip).await
-                }
-            } else {
-                return None;
-            }
+    // firmware from November 2024 onwards only supports the v3 API
+    match fw_version {
+        FirmwareVersion::Dated { .. } if fw_version >= FirmwareVersion::dated(2024, 11, 1) => {
+            get_model_whatsminer_v3(ip, ports).await
         }
-        None => None,
+        FirmwareVersion::Dated { .. } => get_model_whatsminer_v2(ip, ports, responses).await,
+        _ => None,
     }
 }
 
-pub(crate) async fn get_model_bitaxe(ip: IpAddr) -> Option<MinerModel> {
-    let raw_json = util::send_web_command(&ip, "/api/system/info")
-        .await
-        .unwrap()
-        .0;
+pub(crate) async fn get_model_bitaxe(ip: IpAddr, ports: MinerPorts) -> Option<MinerModel> {
+    let raw_json =
+        util::send_web_command(&ip, ports.web_port(80), ports.use_https, "/api/system/info")
+            .await?
+            .0;
     let response: serde_json::Value = serde_json::from_str(&raw_json).ok()?;
 
-    match response["ASICModel"].as_str() {
-        Some("BM1366") => Some(MinerModel::Bitaxe(Ultra)),
-        Some("BM1368") => Some(MinerModel::Bitaxe(Supra)),
-        Some("BM1370") => Some(MinerModel::Bitaxe(Gamma)),
-        Some("BM1387") => Some(MinerModel::Bitaxe(Max)),
+    parse_model_bitaxe(&response)
+        .map(MinerModel::Bitaxe)
+        .or_else(|| {
+            let raw = response["deviceModel"]
+                .as_str()
+                .or(response["ASICModel"].as_str())?;
+            Some(MinerModel::Unknown {
+                make: MinerMake::BitAxe,
+                raw: raw.to_owned(),
+            })
+        })
+}
+
+/// Select an ESP-Miner model from `system/info`.
+///
+/// Forks for multi-ASIC boards report a `deviceModel`, otherwise the board is identified
+/// by its `asicCount` and `boardVersion`, falling back to the single-ASIC model.
+fn parse_model_bitaxe(info: &serde_json::Value) -> Option<BitaxeModel> {
+    let device_model = info["deviceModel"]
+        .as_str()
+        .unwrap_or_default()
+        .to_uppercase()
+        .replace([' ', '-', '_'], "");
+    match device_model.as_str() {
+        "NERDQAXE++" | "NERDQAXEPLUSPLUS" => return Some(NerdQAxePlusPlus),
+        "NERDQAXE+" | "NERDQAXEPLUS" => return Some(NerdQAxePlus),
+        m if m.starts_with("NERDOCTAXE") => return Some(NerdOctaxe),
+        _ => {}
+    }
+
+    let asic_model = info["ASICModel"].as_str();
+    let asic_count = info["asicCount"].as_u64().unwrap_or(1);
+    // Hex boards are 3xx (Ultra Hex) and 7xx (Supra Hex)
+    let board_series = info["boardVersion"]
+        .as_str()
+        .and_then(|v| v.trim().chars().next());
+
+    match (asic_model, asic_count, board_series) {
+        (Some("BM1366"), 6, _) | (Some("BM1366"), _, Some('3')) => Some(Hex),
+        (Some("BM1368"), 6, _) | (Some("BM1368"), _, Some('7')) => Some(SupraHex),
+        (Some("BM1368"), 4, _) => Some(NerdQAxePlus),
+        (Some("BM1370"), 4, _) => Some(NerdQAxePlusPlus),
+        (Some("BM1370"), 8, _) => Some(NerdOctaxe),
+        (Some("BM1366"), _, _) => Some(Ultra),
+        (Some("BM1368"), _, _) => Some(Supra),
+        (Some("BM1370"), _, _) => Some(Gamma),
+        (Some("BM1387"), _, _) => Some(Max),
         _ => None,
     }
 }
 
-pub(crate) async fn get_model_luxos(ip: IpAddr) -> Option<MinerModel> {
-    let response = util::send_rpc_command(&ip, "version").await;
+pub(crate) async fn get_model_goldshell(ip: IpAddr, ports: MinerPorts) -> Option<MinerModel> {
+    // newer firmware requires a token for /mcb/status, so log in like the backend does
+    let response = GoldshellWebApi::new(ip.to_string(), ports.web_port(80))
+        .with_https(ports.use_https)
+        .status()
+        .await
+        .ok()?;
+
+    let raw = response["model"].as_str()?;
+    let model = raw
+        .to_uppercase()
+        .replace("GOLDSHELL", "")
+        .replace([' ', '-', '_'], "");
+
+    MinerModelFactory::new()
+        .with_make(MinerMake::Goldshell)
+        .parse_model(&model, raw)
+}
+
+pub(crate) async fn get_model_iceriver(ip: IpAddr, ports: MinerPorts) -> Option<MinerModel> {
+    let response = IceRiverWebApi::new(ip.to_string(), ports.web_port(80))
+        .with_https(ports.use_https)
+        .userpanel()
+        .await
+        .ok()?;
+
+    let raw = response["data"]["model"].as_str()?;
+    let model = raw
+        .to_uppercase()
+        .replace("ICERIVER", "")
+        .replace([' ', '-', '_'], "");
+
+    MinerModelFactory::new()
+        .with_make(MinerMake::IceRiver)
+        .parse_model(&model, raw)
+}
+
+pub(crate) async fn get_model_auradine(ip: IpAddr, ports: MinerPorts) -> Option<MinerModel> {
+    let response = Auradine::web_api(&ip.to_string(), ports)
+        .ipreport()
+        .await
+        .ok()?;
+
+    let raw = response["IPReport"][0]["model"].as_str()?;
+    let model = raw
+        .to_uppercase()
+        .replace("AURADINE", "")
+        .replace("TERAFLUX", "")
+        .replace([' ', '-', '_'], "");
+
+    MinerModelFactory::new()
+        .with_make(MinerMake::Auradine)
+        .parse_model(&model, raw)
+}
+
+pub(crate) async fn get_model_cgminer(
+    ip: IpAddr,
+    make: MinerMake,
+    ports: MinerPorts,
+    responses: &DiscoveryResponses,
+) -> Option<MinerModel> {
+    let response = send_rpc_command(&ip, ports, "version", responses).await?;
+
+    let make_name = serde_json::to_value(make).ok()?;
+    let raw = response["VERSION"][0]["Type"].as_str()?;
+    let model = raw
+        .to_uppercase()
+        .replace(&make_name.as_str()?.to_uppercase(), "")
+        .replace([' ', '-', '_'], "");
+
+    MinerModelFactory::new()
+        .with_make(make)
+        .parse_model(&model, raw)
+}
+
+pub(crate) async fn get_model_marathon(ip: IpAddr, ports: MinerPorts) -> Option<MinerModel> {
+    let response = MarathonWebApi::new(ip.to_string(), ports.web_port(80))
+        .with_https(ports.use_https)
+        .overview()
+        .await
+        .ok()?;
+
+    parse_model_marathon(&response)
+}
+
+/// Parse the model from the MARA firmware `overview` response
+fn parse_model_marathon(overview: &serde_json::Value) -> Option<MinerModel> {
+    // model names are matched with their make prefix, which MARA firmware can leave off
+    let raw = overview["model"].as_str()?;
+    let model = raw.to_uppercase();
+    let model = match model.starts_with("ANTMINER ") {
+        true => model,
+        false => format!("ANTMINER {}", model),
+    };
+
+    MinerModelFactory::new()
+        .with_firmware(MinerFirmware::Marathon)
+        .parse_model(&model, raw)
+}
+
+pub(crate) async fn get_model_luxos(
+    ip: IpAddr,
+    ports: MinerPorts,
+    responses: &DiscoveryResponses,
+) -> Option<MinerModel> {
+    let response = send_rpc_command(&ip, ports, "version", responses).await;
     match response {
         Some(json_data) => {
             let model = json_data["VERSION"][0]["Type"].as_str();
             if model.is_none() {
                 return None;
             }
-            let model = model.unwrap().to_uppercase();
+            let raw = model.unwrap();
+            let model = raw.to_uppercase();
 
             MinerModelFactory::new()
                 .with_firmware(MinerFirmware::LuxOS)
-                .parse_model(&model)
+                .parse_model(&model, raw)
         }
         None => None,
     }
 }
 
-pub(crate) async fn get_model_braiins_os(ip: IpAddr) -> Option<MinerModel> {
-    let response = util::send_rpc_command(&ip, "devdetails").await;
+pub(crate) async fn get_model_braiins_os(
+    ip: IpAddr,
+    ports: MinerPorts,
+    responses: &DiscoveryResponses,
+) -> Option<MinerModel> {
+    let response = send_rpc_command(&ip, ports, "devdetails", responses).await;
     match response {
         Some(json_data) => {
             let model = json_data["DEVDETAILS"][0]["Model"].as_str();
             if model.is_none() {
                 return None;
             }
-            let model = model
-                .unwrap()
+            let raw = model.unwrap();
+            let model = raw
                 .to_uppercase()
                 .replace("BITMAIN ", "")
                 .replace("S19XP", "S19 XP");
 
             MinerModelFactory::new()
                 .with_firmware(MinerFirmware::BraiinsOS)
-                .parse_model(&model)
+                .parse_model(&model, raw)
         }
         None => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::device::models::antminer::AntMinerModel;
+    use crate::data::device::models::whatsminer::WhatsMinerModel;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_model_bitaxe() {
+        let single = json!({"ASICModel": "BM1370", "asicCount": 1, "boardVersion": "601"});
+        assert_eq!(parse_model_bitaxe(&single), Some(Gamma));
+
+        let hex = json!({"ASICModel": "BM1366", "asicCount": 6, "boardVersion": "302"});
+        assert_eq!(parse_model_bitaxe(&hex), Some(Hex));
+
+        let nerd = json!({"ASICModel": "BM1370", "asicCount": 4, "deviceModel": "NerdQAxe++"});
+        assert_eq!(parse_model_bitaxe(&nerd), Some(NerdQAxePlusPlus));
+
+        let octaxe = json!({"ASICModel": "BM1370", "asicCount": 8});
+        assert_eq!(parse_model_bitaxe(&octaxe), Some(NerdOctaxe));
+    }
+
+    // a documentation address, so a request that is not served from the responses fails
+    const UNREACHABLE: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 0, 2, 1));
+
+    #[tokio::test]
+    async fn test_get_model_antminer_from_responses() {
+        let mut responses = DiscoveryResponses::new();
+        responses.insert(
+            MinerCommand::RPC { command: "version" },
+            vec![DiscoveryResponse::RPC(json!({
+                "VERSION": [{"BMMiner": "1.0.0", "Type": "Antminer S19j Pro"}]
+            }))],
+        );
+        assert_eq!(
+            get_model_antminer(UNREACHABLE, MinerPorts::default(), &responses).await,
+            Some(MinerModel::AntMiner(AntMinerModel::S19jPro))
+        );
+
+        // HiveOS and MSKMiner are read as AntMiners, with the firmware suffix dropped
+        let mut responses = DiscoveryResponses::new();
+        responses.insert(
+            MinerCommand::RPC { command: "version" },
+            vec![DiscoveryResponse::RPC(json!({
+                "VERSION": [{"BMMiner": "1.0.0", "Type": "Antminer S19 (Hiveon)"}]
+            }))],
+        );
+        assert_eq!(
+            get_model_antminer(UNREACHABLE, MinerPorts::default(), &responses).await,
+            Some(MinerModel::AntMiner(AntMinerModel::S19))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_model_whatsminer_from_responses() {
+        let mut responses = DiscoveryResponses::new();
+        responses.insert(
+            MinerCommand::RPC {
+                command: "devdetails",
+            },
+            vec![DiscoveryResponse::RPC(json!({
+                "DEVDETAILS": [{"DEVDETAILS": 0, "Name": "SM", "Model": "M30S+_VE43"}]
+            }))],
+        );
+
+        assert_eq!(
+            get_model_whatsminer(UNREACHABLE, MinerPorts::default(), &responses).await,
+            Some(MinerModel::WhatsMiner(WhatsMinerModel::M30SPlusVE40))
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_whatsminer_model_keeps_raw() {
+        assert_eq!(
+            whatsminer::parse_model("M99S++_VZ42"),
+            Some(MinerModel::Unknown {
+                make: MinerMake::WhatsMiner,
+                raw: String::from("M99S++_VZ42"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_model_marathon() {
+        let s21 = Some(MinerModel::AntMiner(AntMinerModel::S21));
+
+        // the prefix must not be doubled up when the firmware reports it
+        let prefixed = json!({"model": "ANTMINER S21", "version_firmware": "1.6.2"});
+        assert_eq!(parse_model_marathon(&prefixed), s21);
+
+        let bare = json!({"model": "S21", "version_firmware": "1.6.2"});
+        assert_eq!(parse_model_marathon(&bare), s21);
+
+        let mixed_case = json!({"model": "Antminer S21"});
+        assert_eq!(parse_model_marathon(&mixed_case), s21);
+
+        assert_eq!(parse_model_marathon(&json!({})), None);
+    }
+}
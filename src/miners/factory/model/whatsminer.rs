@@ -1,52 +1,42 @@
 use crate::data::device::models::MinerModelFactory;
 use crate::data::device::{MinerMake, MinerModel};
 use crate::miners::api::rpc::{btminer::BTMinerV3RPC, traits::SendRPCCommand};
-use crate::miners::util;
+use crate::miners::factory::DiscoveryResponses;
+use crate::miners::ports::MinerPorts;
 use serde_json::Value;
 use std::net::IpAddr;
 
-pub(crate) async fn get_model_whatsminer_v2(ip: IpAddr) -> Option<MinerModel> {
-    let response = util::send_rpc_command(&ip, "devdetails").await;
+pub(crate) async fn get_model_whatsminer_v2(
+    ip: IpAddr,
+    ports: MinerPorts,
+    responses: &DiscoveryResponses,
+) -> Option<MinerModel> {
+    let response = super::send_rpc_command(&ip, ports, "devdetails", responses).await;
     match response {
-        Some(json_data) => {
-            let model = json_data["DEVDETAILS"][0]["Model"].as_str();
-            if model.is_none() {
-                return None;
-            }
-            let mut model = model.unwrap().to_uppercase().replace("_", "");
-            model.pop();
-            model.push('0');
-
-            MinerModelFactory::new()
-                .with_make(MinerMake::WhatsMiner)
-                .parse_model(&model)
-        }
+        Some(json_data) => parse_model(json_data["DEVDETAILS"][0]["Model"].as_str()?),
         None => None,
     }
 }
 
-pub(crate) async fn get_model_whatsminer_v3(ip: IpAddr) -> Option<MinerModel> {
-    let rpc = BTMinerV3RPC::new(ip, None);
+pub(crate) async fn get_model_whatsminer_v3(ip: IpAddr, ports: MinerPorts) -> Option<MinerModel> {
+    let rpc = BTMinerV3RPC::new(ip, Some(ports.btminer_port()));
     let response = rpc
         .send_command::<Value, &str>("get.device.info", Some("miner"))
         .await;
 
     match response {
-        Ok(json_data) => {
-            let model = json_data["msg"]["miner"]["type"].as_str();
-
-            if model.is_none() {
-                return None;
-            }
-
-            let mut model = model.unwrap().to_uppercase().replace("_", "");
-            model.pop();
-            model.push('0');
-
-            MinerModelFactory::new()
-                .with_make(MinerMake::WhatsMiner)
-                .parse_model(&model)
-        }
+        Ok(json_data) => parse_model(json_data["msg"]["miner"]["type"].as_str()?),
         Err(_) => None,
     }
 }
+
+/// Parse a WhatsMiner model such as `M30S+_VE43`, where the last digit is a hardware revision
+pub(crate) fn parse_model(raw: &str) -> Option<MinerModel> {
+    let mut model = raw.to_uppercase().replace("_", "");
+    model.pop();
+    model.push('0');
+
+    MinerModelFactory::new()
+        .with_make(MinerMake::WhatsMiner)
+        .parse_model(&model, raw)
+}
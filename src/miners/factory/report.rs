@@ -0,0 +1,187 @@
+use std::fmt::Display;
+use std::net::IpAddr;
+
+use super::detector::DiscoveryResponse;
+use crate::data::device::{MinerFirmware, MinerMake, MinerModel};
+
+/// A protocol a miner answered a discovery command on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiscoveryProtocol {
+    /// The RPC API on port 4028
+    RPC,
+    /// A web server over plain HTTP
+    HTTP,
+    /// A web server over HTTPS
+    HTTPS,
+}
+
+impl DiscoveryProtocol {
+    /// The protocol a discovery response was received over
+    pub(crate) fn from_response(response: &DiscoveryResponse) -> Self {
+        match response {
+            DiscoveryResponse::RPC(_) => DiscoveryProtocol::RPC,
+            DiscoveryResponse::Web { https: false, .. } => DiscoveryProtocol::HTTP,
+            DiscoveryResponse::Web { https: true, .. } => DiscoveryProtocol::HTTPS,
+        }
+    }
+}
+
+/// Why discovery did not produce a backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscoveryFailure {
+    /// Nothing answered any discovery command
+    NoResponse,
+    /// Something answered, but no make or firmware matched the responses
+    Unidentified,
+    /// The make or firmware was identified, but the model could not be read
+    ModelNotFound,
+    /// No backend supports the identified make, firmware and model
+    Unsupported,
+    /// A custom detector matched, but its constructor did not return a backend
+    DetectorFailed,
+}
+
+impl Display for DiscoveryFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiscoveryFailure::NoResponse => write!(f, "No response"),
+            DiscoveryFailure::Unidentified => write!(f, "Responded, but was not identified"),
+            DiscoveryFailure::ModelNotFound => write!(f, "Identified, but the model was not found"),
+            DiscoveryFailure::Unsupported => write!(f, "No backend for this miner"),
+            DiscoveryFailure::DetectorFailed => write!(f, "Custom detector failed to build"),
+        }
+    }
+}
+
+/// Everything discovery learned about an IP address, whether or not a backend was selected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryReport {
+    /// The IP address that was scanned
+    pub ip: IpAddr,
+    /// The hostname the IP address was resolved from, if the target was given by name
+    pub target_name: Option<String>,
+    /// The protocols that answered within the discovery timeout
+    pub protocols: Vec<DiscoveryProtocol>,
+    /// The make, if it was identified
+    pub make: Option<MinerMake>,
+    /// The firmware, if it was identified
+    pub firmware: Option<MinerFirmware>,
+    /// The model, if it could be read
+    pub model: Option<MinerModel>,
+    /// The model string reported by the miner, when it did not match a known model
+    pub raw_model: Option<String>,
+    /// The name of the custom detector that matched, if any
+    pub detector: Option<String>,
+    /// Why no backend was selected, `None` if one was
+    pub failure: Option<DiscoveryFailure>,
+}
+
+impl DiscoveryReport {
+    pub(crate) fn new(ip: IpAddr) -> Self {
+        Self {
+            ip,
+            target_name: None,
+            protocols: vec![],
+            make: None,
+            firmware: None,
+            model: None,
+            raw_model: None,
+            detector: None,
+            failure: None,
+        }
+    }
+
+    /// Whether a backend was selected for this miner
+    pub fn is_supported(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// Whether anything answered on this IP address
+    pub fn responded(&self) -> bool {
+        !self.protocols.is_empty()
+    }
+
+    /// Why no backend was selected, given what this report identified.
+    ///
+    /// Returns `None` when `selected` is true.
+    pub(crate) fn classify(&self, selected: bool) -> Option<DiscoveryFailure> {
+        if selected {
+            return None;
+        }
+        if self.detector.is_some() {
+            return Some(DiscoveryFailure::DetectorFailed);
+        }
+        if self.make.is_none() && self.firmware.is_none() {
+            return Some(match self.responded() {
+                true => DiscoveryFailure::Unidentified,
+                false => DiscoveryFailure::NoResponse,
+            });
+        }
+        Some(match self.model {
+            None => DiscoveryFailure::ModelNotFound,
+            Some(_) => DiscoveryFailure::Unsupported,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::device::models::antminer::AntMinerModel;
+
+    fn report() -> DiscoveryReport {
+        DiscoveryReport::new(IpAddr::from([10, 0, 0, 1]))
+    }
+
+    #[test]
+    fn test_classify_no_response() {
+        assert_eq!(report().classify(false), Some(DiscoveryFailure::NoResponse));
+    }
+
+    #[test]
+    fn test_classify_unidentified() {
+        let mut report = report();
+        report.protocols = vec![DiscoveryProtocol::HTTP];
+        assert_eq!(report.classify(false), Some(DiscoveryFailure::Unidentified));
+    }
+
+    #[test]
+    fn test_classify_model_not_found() {
+        let mut report = report();
+        report.protocols = vec![DiscoveryProtocol::RPC];
+        report.make = Some(MinerMake::AntMiner);
+        assert_eq!(
+            report.classify(false),
+            Some(DiscoveryFailure::ModelNotFound)
+        );
+
+        report.make = None;
+        report.firmware = Some(MinerFirmware::BraiinsOS);
+        assert_eq!(
+            report.classify(false),
+            Some(DiscoveryFailure::ModelNotFound)
+        );
+    }
+
+    #[test]
+    fn test_classify_unsupported() {
+        let mut report = report();
+        report.protocols = vec![DiscoveryProtocol::RPC];
+        report.make = Some(MinerMake::AntMiner);
+        report.model = Some(MinerModel::AntMiner(AntMinerModel::S19));
+        assert_eq!(report.classify(false), Some(DiscoveryFailure::Unsupported));
+        assert_eq!(report.classify(true), None);
+    }
+
+    #[test]
+    fn test_classify_detector_failed() {
+        let mut report = report();
+        report.protocols = vec![DiscoveryProtocol::HTTP];
+        report.detector = Some("custom".to_owned());
+        assert_eq!(
+            report.classify(false),
+            Some(DiscoveryFailure::DetectorFailed)
+        );
+        assert_eq!(report.classify(true), None);
+    }
+}
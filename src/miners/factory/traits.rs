@@ -1,15 +1,23 @@
+use super::DiscoveryResponses;
 use super::commands::{HTTP_WEB_ROOT, RPC_DEVDETAILS, RPC_VERSION};
 use super::model;
 use crate::data::device::models::MinerModel;
 use crate::data::device::{MinerFirmware, MinerMake};
 use crate::miners::commands::MinerCommand;
+use crate::miners::ports::MinerPorts;
 use std::net::IpAddr;
 
 pub(crate) trait DiscoveryCommands {
     fn get_discovery_commands(&self) -> Vec<MinerCommand>;
 }
 pub(crate) trait ModelSelection {
-    async fn get_model(&self, ip: IpAddr) -> Option<MinerModel>;
+    /// Read the model, reusing discovery responses where they already contain it
+    async fn get_model(
+        &self,
+        ip: IpAddr,
+        ports: MinerPorts,
+        responses: &DiscoveryResponses,
+    ) -> Option<MinerModel>;
 }
 
 impl DiscoveryCommands for MinerMake {
@@ -21,6 +29,10 @@ impl DiscoveryCommands for MinerMake {
             MinerMake::EPic => vec![HTTP_WEB_ROOT],
             MinerMake::Braiins => vec![RPC_VERSION, HTTP_WEB_ROOT],
             MinerMake::BitAxe => vec![HTTP_WEB_ROOT],
+            MinerMake::Goldshell => vec![HTTP_WEB_ROOT],
+            MinerMake::IceRiver => vec![HTTP_WEB_ROOT],
+            MinerMake::Auradine => vec![HTTP_WEB_ROOT],
+            MinerMake::Elphapex | MinerMake::Volcminer | MinerMake::Hammer => vec![RPC_VERSION],
         }
     }
 }
@@ -31,29 +43,48 @@ impl DiscoveryCommands for MinerFirmware {
             MinerFirmware::BraiinsOS => vec![RPC_VERSION, HTTP_WEB_ROOT],
             MinerFirmware::VNish => vec![HTTP_WEB_ROOT, RPC_VERSION],
             MinerFirmware::EPic => vec![HTTP_WEB_ROOT],
-            MinerFirmware::HiveOS => vec![],
+            MinerFirmware::HiveOS => vec![RPC_VERSION, HTTP_WEB_ROOT],
             MinerFirmware::LuxOS => vec![HTTP_WEB_ROOT, RPC_VERSION],
-            MinerFirmware::Marathon => vec![],
-            MinerFirmware::MSKMiner => vec![],
+            MinerFirmware::Marathon => vec![HTTP_WEB_ROOT],
+            MinerFirmware::MSKMiner => vec![RPC_VERSION, HTTP_WEB_ROOT],
         }
     }
 }
 impl ModelSelection for MinerFirmware {
-    async fn get_model(&self, ip: IpAddr) -> Option<MinerModel> {
+    async fn get_model(
+        &self,
+        ip: IpAddr,
+        ports: MinerPorts,
+        responses: &DiscoveryResponses,
+    ) -> Option<MinerModel> {
         match self {
-            MinerFirmware::LuxOS => model::get_model_luxos(ip).await,
-            MinerFirmware::BraiinsOS => model::get_model_braiins_os(ip).await,
+            MinerFirmware::LuxOS => model::get_model_luxos(ip, ports, responses).await,
+            MinerFirmware::BraiinsOS => model::get_model_braiins_os(ip, ports, responses).await,
+            MinerFirmware::Marathon => model::get_model_marathon(ip, ports).await,
+            // HiveOS and MSKMiner are identified as AntMiners, so the make reads their model
             _ => None,
         }
     }
 }
 
 impl ModelSelection for MinerMake {
-    async fn get_model(&self, ip: IpAddr) -> Option<MinerModel> {
+    async fn get_model(
+        &self,
+        ip: IpAddr,
+        ports: MinerPorts,
+        responses: &DiscoveryResponses,
+    ) -> Option<MinerModel> {
         match self {
-            MinerMake::AntMiner => model::get_model_antminer(ip).await,
-            MinerMake::WhatsMiner => model::get_model_whatsminer(ip).await,
-            MinerMake::BitAxe => model::get_model_bitaxe(ip).await,
+            MinerMake::AntMiner => model::get_model_antminer(ip, ports, responses).await,
+            MinerMake::WhatsMiner => model::get_model_whatsminer(ip, ports, responses).await,
+            MinerMake::BitAxe => model::get_model_bitaxe(ip, ports).await,
+            MinerMake::Goldshell => model::get_model_goldshell(ip, ports).await,
+            MinerMake::IceRiver => model::get_model_iceriver(ip, ports).await,
+            MinerMake::Auradine => model::get_model_auradine(ip, ports).await,
+            MinerMake::Braiins => model::get_model_braiins_os(ip, ports, responses).await,
+            MinerMake::Elphapex | MinerMake::Volcminer | MinerMake::Hammer => {
+                model::get_model_cgminer(ip, *self, ports, responses).await
+            }
             _ => None,
         }
     }
@@ -0,0 +1,357 @@
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use futures::{SinkExt, Stream, StreamExt};
+use macaddr::MacAddr;
+use tokio::net::UdpSocket;
+use tokio::task::JoinSet;
+
+use crate::data::device::MinerMake;
+use crate::miners::backends::traits::GetMinerData;
+use crate::miners::factory::MinerFactory;
+
+const ANTMINER_REPORT_PORT: u16 = 14235;
+const WHATSMINER_REPORT_PORT: u16 = 8888;
+const DEFAULT_IDENTIFY_TIMEOUT: Duration = Duration::from_secs(30);
+const REPORT_BUFFER: usize = 64;
+const MAX_PACKET_SIZE: usize = 1024;
+
+/// A decoded IP report, sent by a miner when its IP report button is pressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpReport {
+    /// The IP address of the miner
+    pub ip: IpAddr,
+    /// The MAC address of the miner, if the packet included one
+    pub mac: Option<MacAddr>,
+    /// The make, from the port the report was received on
+    pub make: MinerMake,
+}
+
+impl IpReport {
+    /// Decode an IP report packet.
+    ///
+    /// AntMiners send `ip,mac` and WhatsMiners send `IP:ipMAC:mac`, some firmware sends
+    /// JSON instead. The sender's address is used if the packet has no IP address in it.
+    pub fn decode(make: MinerMake, payload: &[u8], source: IpAddr) -> Option<Self> {
+        let text = String::from_utf8_lossy(payload);
+        let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        if text.is_empty() {
+            return None;
+        }
+
+        let (ip, mac) = match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(json) => {
+                let field = |key: &str| {
+                    json[key]
+                        .as_str()
+                        .or(json[key.to_uppercase()].as_str())
+                        .map(String::from)
+                };
+                (field("ip"), field("mac"))
+            }
+            Err(_) => decode_text(text),
+        };
+
+        Some(Self {
+            ip: ip.and_then(|ip| ip.parse().ok()).unwrap_or(source),
+            mac: mac.and_then(|mac| MacAddr::from_str(&mac).ok()),
+            make,
+        })
+    }
+}
+
+/// Find the IP and MAC address in a plain text report
+fn decode_text(text: &str) -> (Option<String>, Option<String>) {
+    if let (Some(ip_start), Some(mac_start)) = (
+        find_ignore_case(text, "IP:"),
+        find_ignore_case(text, "MAC:"),
+    ) {
+        let ip_end = match mac_start > ip_start {
+            true => mac_start,
+            false => text.len(),
+        };
+        let ip = text[ip_start + 3..ip_end].trim_matches([',', ' ']);
+        let mac = text[mac_start + 4..].trim().chars().take(17).collect();
+        return (Some(ip.to_owned()), Some(mac));
+    }
+
+    let fields: Vec<&str> = text.split([',', ' ', ';', '\n']).map(str::trim).collect();
+    let ip = fields.iter().find(|f| f.parse::<IpAddr>().is_ok());
+    let mac = fields.iter().find(|f| MacAddr::from_str(f).is_ok());
+    (ip.map(|f| f.to_string()), mac.map(|f| f.to_string()))
+}
+
+/// The byte offset of an ASCII `needle` in `text`, ignoring case.
+///
+/// The match is ASCII, so the offsets around it are always character boundaries.
+fn find_ignore_case(text: &str, needle: &str) -> Option<usize> {
+    text.as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// A miner that reported its IP address, identified by the factory if one was given.
+pub struct ReportedMiner {
+    pub report: IpReport,
+    /// The miner handle, `None` if the listener has no factory or the miner was not identified
+    pub miner: Option<Box<dyn GetMinerData>>,
+}
+
+/// Listens for the UDP broadcasts miners send when their IP report button is pressed.
+///
+/// ```no_run
+/// use asic_rs::miners::factory::MinerFactory;
+/// use asic_rs::miners::listener::IpReportListener;
+/// use futures::StreamExt;
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let mut reports = IpReportListener::new()
+///     .with_factory(MinerFactory::new())
+///     .start()
+///     .await?;
+/// while let Some(reported) = reports.next().await {
+///     println!("{} pressed IP report", reported.report.ip);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct IpReportListener {
+    ports: Vec<(MinerMake, u16)>,
+    factory: Option<MinerFactory>,
+    identify_timeout: Duration,
+}
+
+impl Default for IpReportListener {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IpReportListener {
+    /// Create a listener on the standard AntMiner and WhatsMiner report ports
+    pub fn new() -> Self {
+        Self {
+            ports: vec![
+                (MinerMake::AntMiner, ANTMINER_REPORT_PORT),
+                (MinerMake::WhatsMiner, WHATSMINER_REPORT_PORT),
+            ],
+            factory: None,
+            identify_timeout: DEFAULT_IDENTIFY_TIMEOUT,
+        }
+    }
+
+    /// Set the port reports from a make are received on, `0` picks a free port
+    pub fn with_port(mut self, make: MinerMake, port: u16) -> Self {
+        self.ports.retain(|(m, _)| *m != make);
+        self.ports.push((make, port));
+        self
+    }
+
+    /// Identify every reporting miner with a factory
+    pub fn with_factory(mut self, factory: MinerFactory) -> Self {
+        self.factory = Some(factory);
+        self
+    }
+
+    /// Set how long identifying a reporting miner may take
+    pub fn with_identify_timeout(mut self, timeout: Duration) -> Self {
+        self.identify_timeout = timeout;
+        self
+    }
+
+    /// Bind the report ports and start listening in the background.
+    ///
+    /// Listening stops when the returned stream is dropped.
+    pub async fn start(self) -> std::io::Result<IpReportStream> {
+        let (sender, receiver) = mpsc::channel(REPORT_BUFFER);
+        let factory = self.factory.map(Arc::new);
+        let mut local_addrs = vec![];
+        let mut tasks = JoinSet::new();
+
+        for (make, port) in self.ports {
+            let socket = UdpSocket::bind((IpAddr::from([0, 0, 0, 0]), port)).await?;
+            local_addrs.push((make, socket.local_addr()?));
+            tasks.spawn(listen(
+                socket,
+                make,
+                factory.clone(),
+                self.identify_timeout,
+                sender.clone(),
+            ));
+        }
+
+        Ok(IpReportStream {
+            receiver,
+            local_addrs,
+            _tasks: tasks,
+        })
+    }
+}
+
+async fn listen(
+    socket: UdpSocket,
+    make: MinerMake,
+    factory: Option<Arc<MinerFactory>>,
+    identify_timeout: Duration,
+    mut sender: mpsc::Sender<ReportedMiner>,
+) {
+    let mut buffer = [0u8; MAX_PACKET_SIZE];
+    let mut identifying = JoinSet::new();
+    loop {
+        let Ok((len, source)) = socket.recv_from(&mut buffer).await else {
+            continue;
+        };
+        let Some(report) = IpReport::decode(make, &buffer[..len], source.ip()) else {
+            continue;
+        };
+
+        let Some(factory) = factory.clone() else {
+            if sender
+                .send(ReportedMiner {
+                    report,
+                    miner: None,
+                })
+                .await
+                .is_err()
+            {
+                return;
+            }
+            continue;
+        };
+        // identify in the background, so presses on other miners are not held up
+        let mut sender = sender.clone();
+        identifying.spawn(async move {
+            let miner = tokio::time::timeout(identify_timeout, factory.get_miner(report.ip))
+                .await
+                .ok()
+                .and_then(|miner| miner.ok())
+                .flatten();
+            let _ = sender.send(ReportedMiner { report, miner }).await;
+        });
+        while identifying.try_join_next().is_some() {}
+    }
+}
+
+/// Stream of [`ReportedMiner`]s from a running [`IpReportListener`].
+pub struct IpReportStream {
+    receiver: mpsc::Receiver<ReportedMiner>,
+    local_addrs: Vec<(MinerMake, SocketAddr)>,
+    _tasks: JoinSet<()>,
+}
+
+impl IpReportStream {
+    /// The addresses being listened on, by the make reported on each
+    pub fn local_addrs(&self) -> &[(MinerMake, SocketAddr)] {
+        &self.local_addrs
+    }
+
+    /// Discard identification and only yield the decoded reports
+    pub fn reports(self) -> impl Stream<Item = IpReport> {
+        self.map(|reported| reported.report)
+    }
+}
+
+impl Stream for IpReportStream {
+    type Item = ReportedMiner;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_ip_reports() {
+        let source = IpAddr::from([10, 0, 0, 1]);
+        let mac = MacAddr::from_str("c4:08:28:00:11:22").ok();
+
+        let antminer =
+            IpReport::decode(MinerMake::AntMiner, b"10.0.0.5,C4:08:28:00:11:22", source).unwrap();
+        assert_eq!(antminer.ip, IpAddr::from([10, 0, 0, 5]));
+        assert_eq!(antminer.mac, mac);
+
+        let whatsminer = IpReport::decode(
+            MinerMake::WhatsMiner,
+            b"IP:10.0.0.6MAC:C4:08:28:00:11:22\0\0",
+            source,
+        )
+        .unwrap();
+        assert_eq!(whatsminer.ip, IpAddr::from([10, 0, 0, 6]));
+        assert_eq!(whatsminer.mac, mac);
+
+        let json = IpReport::decode(
+            MinerMake::WhatsMiner,
+            br#"{"IP": "10.0.0.7", "MAC": "C4:08:28:00:11:22"}"#,
+            source,
+        )
+        .unwrap();
+        assert_eq!(json.ip, IpAddr::from([10, 0, 0, 7]));
+
+        // characters that change length when uppercased must not shift the offsets
+        let garbled = IpReport::decode(
+            MinerMake::WhatsMiner,
+            "ſſſıIP:10.0.0.8MAC:C4:08:28:00:11:22".as_bytes(),
+            source,
+        )
+        .unwrap();
+        assert_eq!(garbled.ip, IpAddr::from([10, 0, 0, 8]));
+        assert_eq!(garbled.mac, mac);
+
+        let no_ip = IpReport::decode(MinerMake::AntMiner, b"C4:08:28:00:11:22", source).unwrap();
+        assert_eq!(no_ip.ip, source);
+        assert_eq!(IpReport::decode(MinerMake::AntMiner, b"\0\0", source), None);
+    }
+
+    #[tokio::test]
+    async fn test_listen_for_ip_reports() {
+        let stream = IpReportListener::new()
+            .with_port(MinerMake::AntMiner, 0)
+            .with_port(MinerMake::WhatsMiner, 0)
+            .start()
+            .await
+            .unwrap();
+        let port = |make| {
+            stream
+                .local_addrs()
+                .iter()
+                .find(|(m, _)| *m == make)
+                .map(|(_, addr)| addr.port())
+                .unwrap()
+        };
+        let antminer_port = port(MinerMake::AntMiner);
+        let whatsminer_port = port(MinerMake::WhatsMiner);
+
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        sender
+            .send_to(b"10.0.0.5,C4:08:28:00:11:22", ("127.0.0.1", antminer_port))
+            .await
+            .unwrap();
+        sender
+            .send_to(
+                b"IP:10.0.0.6MAC:C4:08:28:00:11:23",
+                ("127.0.0.1", whatsminer_port),
+            )
+            .await
+            .unwrap();
+
+        let mut reports: Vec<IpReport> = tokio::time::timeout(
+            Duration::from_secs(5),
+            stream.reports().take(2).collect::<Vec<_>>(),
+        )
+        .await
+        .unwrap();
+        reports.sort_by_key(|r| r.ip);
+        assert_eq!(reports[0].make, MinerMake::AntMiner);
+        assert_eq!(reports[0].ip, IpAddr::from([10, 0, 0, 5]));
+        assert_eq!(reports[1].make, MinerMake::WhatsMiner);
+        assert_eq!(reports[1].ip, IpAddr::from([10, 0, 0, 6]));
+    }
+}
@@ -3,4 +3,7 @@ pub mod backends;
 pub mod commands;
 pub mod data;
 pub mod factory;
+pub mod listener;
+pub mod ports;
+pub mod target;
 pub(crate) mod util;
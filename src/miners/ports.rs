@@ -0,0 +1,86 @@
+/// The ports a miner's APIs are reachable on, for miners on non-standard ports or behind
+/// port forwarding.
+///
+/// Ports left as `None` use the standard port for the API, so a miner behind NAT only needs
+/// the ports that were remapped:
+///
+/// ```
+/// use asic_rs::miners::ports::MinerPorts;
+///
+/// let ports = MinerPorts::new().with_http(8081).with_rpc(14028);
+/// assert_eq!(ports.http_port(), 8081);
+/// assert_eq!(ports.https_port(), 443);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MinerPorts {
+    /// The CGMiner compatible RPC API, 4028 by default
+    pub rpc: Option<u16>,
+    /// The web server over HTTP, 80 by default
+    pub http: Option<u16>,
+    /// The web server over HTTPS, 443 by default
+    pub https: Option<u16>,
+    /// The WhatsMiner v3 API, 4433 by default
+    pub btminer: Option<u16>,
+    /// Whether backends should use HTTPS for the web API
+    pub use_https: bool,
+}
+
+impl MinerPorts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the port of the RPC API
+    pub fn with_rpc(mut self, port: u16) -> Self {
+        self.rpc = Some(port);
+        self
+    }
+
+    /// Set the port of the web server over HTTP
+    pub fn with_http(mut self, port: u16) -> Self {
+        self.http = Some(port);
+        self
+    }
+
+    /// Set the port of the web server over HTTPS
+    pub fn with_https(mut self, port: u16) -> Self {
+        self.https = Some(port);
+        self
+    }
+
+    /// Set the port of the WhatsMiner v3 API
+    pub fn with_btminer(mut self, port: u16) -> Self {
+        self.btminer = Some(port);
+        self
+    }
+
+    /// Set whether backends use HTTPS for the web API, accepting self-signed certificates
+    pub fn with_use_https(mut self, use_https: bool) -> Self {
+        self.use_https = use_https;
+        self
+    }
+
+    pub fn rpc_port(&self) -> u16 {
+        self.rpc.unwrap_or(4028)
+    }
+
+    pub fn http_port(&self) -> u16 {
+        self.http.unwrap_or(80)
+    }
+
+    pub fn https_port(&self) -> u16 {
+        self.https.unwrap_or(443)
+    }
+
+    pub fn btminer_port(&self) -> u16 {
+        self.btminer.unwrap_or(4433)
+    }
+
+    /// The port for a web API that listens on `default_http` when not remapped
+    pub(crate) fn web_port(&self, default_http: u16) -> u16 {
+        match self.use_https {
+            true => self.https_port(),
+            false => self.http.unwrap_or(default_http),
+        }
+    }
+}
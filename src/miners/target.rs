@@ -0,0 +1,292 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::data::miner::MinerData;
+use crate::miners::backends::auradine::AuradineControl;
+use crate::miners::backends::traits::{GetMinerData, RawCommandError};
+use crate::miners::commands::MinerCommand;
+use crate::miners::data::{DataField, DataLocation};
+use crate::miners::ports::MinerPorts;
+
+/// A miner to connect to, given as an IP address, a hostname, or either with a port.
+///
+/// A port is used for the web server, as that is the port usually forwarded to a miner
+/// behind NAT. Other ports can be set with [`MinerPorts`].
+///
+/// ```
+/// use asic_rs::miners::target::MinerTarget;
+///
+/// let target: MinerTarget = "miner-01.site-a:8081".parse().unwrap();
+/// assert_eq!(target.host(), "miner-01.site-a");
+/// assert_eq!(target.port(), Some(8081));
+/// assert!(target.ip().is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MinerTarget {
+    host: String,
+    port: Option<u16>,
+}
+
+impl MinerTarget {
+    /// The IP address or hostname
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The web server port, if one was given
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// The IP address, if the target was given as one and needs no resolving
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.host.parse().ok()
+    }
+
+    /// The hostname, if the target was not given as an IP address
+    pub fn hostname(&self) -> Option<&str> {
+        match self.ip() {
+            Some(_) => None,
+            None => Some(&self.host),
+        }
+    }
+
+    /// The IP address to report in miner data, unspecified for a hostname as backends
+    /// connect to it by name
+    pub(crate) fn data_ip(&self) -> IpAddr {
+        self.ip().unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+    }
+
+    /// The ports to reach this target on, with the target's port as the web server
+    pub(crate) fn ports(&self, ports: MinerPorts) -> MinerPorts {
+        match (self.port, ports.use_https) {
+            (Some(port), true) => ports.with_https(port),
+            (Some(port), false) => ports.with_http(port),
+            (None, _) => ports,
+        }
+    }
+}
+
+impl From<IpAddr> for MinerTarget {
+    fn from(ip: IpAddr) -> Self {
+        Self {
+            host: ip.to_string(),
+            port: None,
+        }
+    }
+}
+
+impl From<SocketAddr> for MinerTarget {
+    fn from(addr: SocketAddr) -> Self {
+        Self {
+            host: addr.ip().to_string(),
+            port: Some(addr.port()),
+        }
+    }
+}
+
+impl FromStr for MinerTarget {
+    type Err = TargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(ip) = s.parse::<IpAddr>() {
+            return Ok(ip.into());
+        }
+        if let Ok(addr) = s.parse::<SocketAddr>() {
+            return Ok(addr.into());
+        }
+
+        let (host, port) = match s.split_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse()
+                    .map_err(|_| TargetError::InvalidTarget(s.to_owned()))?;
+                (host, Some(port))
+            }
+            None => (s, None),
+        };
+        let valid_hostname = !host.is_empty()
+            && host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'));
+        if !valid_hostname {
+            return Err(TargetError::InvalidTarget(s.to_owned()));
+        }
+
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+        })
+    }
+}
+
+impl TryFrom<&str> for MinerTarget {
+    type Error = TargetError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for MinerTarget {
+    type Error = TargetError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Display for MinerTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.port, self.ip()) {
+            (Some(port), Some(IpAddr::V6(ip))) => write!(f, "[{}]:{}", ip, port),
+            (Some(port), _) => write!(f, "{}:{}", self.host, port),
+            (None, _) => write!(f, "{}", self.host),
+        }
+    }
+}
+
+/// Resolves hostnames to the IP address of a miner.
+///
+/// The default [`SystemResolver`] uses the operating system, a custom resolver can instead
+/// look miners up in an inventory or a specific DNS server.
+#[async_trait]
+pub trait MinerResolver: Send + Sync {
+    async fn resolve(&self, host: &str) -> Result<IpAddr, TargetError>;
+}
+
+/// Resolves hostnames with the operating system's resolver, preferring IPv4 addresses
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemResolver;
+
+#[async_trait]
+impl MinerResolver for SystemResolver {
+    async fn resolve(&self, host: &str) -> Result<IpAddr, TargetError> {
+        let addresses: Vec<IpAddr> = tokio::net::lookup_host((host, 0))
+            .await
+            .map_err(|e| TargetError::ResolveFailed(format!("{}: {}", host, e)))?
+            .map(|addr| addr.ip())
+            .collect();
+
+        addresses
+            .iter()
+            .find(|ip| ip.is_ipv4())
+            .or(addresses.first())
+            .copied()
+            .ok_or_else(|| TargetError::ResolveFailed(format!("{}: no addresses", host)))
+    }
+}
+
+/// A miner handle for a target given by hostname, which adds the name to its `MinerData`.
+pub struct NamedMiner {
+    miner: Box<dyn GetMinerData>,
+    name: String,
+}
+
+impl NamedMiner {
+    pub fn new(name: &str, miner: Box<dyn GetMinerData>) -> Self {
+        Self {
+            miner,
+            name: name.to_owned(),
+        }
+    }
+
+    /// The hostname this miner was found by
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Unwrap the underlying miner handle
+    pub fn into_inner(self) -> Box<dyn GetMinerData> {
+        self.miner
+    }
+}
+
+#[async_trait]
+impl GetMinerData for NamedMiner {
+    async fn get_data_fields(&self, fields: &[DataField]) -> MinerData {
+        let mut data = self.miner.get_data_fields(fields).await;
+        data.target_name = Some(self.name.clone());
+        data
+    }
+
+    fn get_locations(&self, data_field: DataField) -> &'static [DataLocation] {
+        self.miner.get_locations(data_field)
+    }
+
+    async fn send_raw(
+        &self,
+        command: MinerCommand,
+        params: Option<Value>,
+    ) -> Result<Value, RawCommandError> {
+        self.miner.send_raw(command, params).await
+    }
+
+    fn as_auradine(&self) -> Option<&dyn AuradineControl> {
+        self.miner.as_auradine()
+    }
+}
+
+/// Error types for parsing and resolving miner targets
+#[derive(Debug, Clone)]
+pub enum TargetError {
+    /// The target is not an IP address or hostname, with an optional port
+    InvalidTarget(String),
+    /// The hostname could not be resolved
+    ResolveFailed(String),
+}
+
+impl Display for TargetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TargetError::InvalidTarget(target) => write!(f, "Invalid target: {}", target),
+            TargetError::ResolveFailed(msg) => write!(f, "Failed to resolve: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for TargetError {}
+
+// lets infallible conversions, such as from an `IpAddr`, be used where a target is parsed
+impl From<Infallible> for TargetError {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        let ip: MinerTarget = "10.0.0.5".parse().unwrap();
+        assert_eq!(ip.ip(), Some(IpAddr::from([10, 0, 0, 5])));
+        assert_eq!(ip.hostname(), None);
+
+        let forwarded: MinerTarget = "203.0.113.7:8081".parse().unwrap();
+        assert_eq!(forwarded.port(), Some(8081));
+        assert_eq!(forwarded.ports(MinerPorts::new()).http_port(), 8081);
+
+        let v6: MinerTarget = "[fd00::5]:8080".parse().unwrap();
+        assert_eq!(v6.to_string(), "[fd00::5]:8080");
+
+        let name: MinerTarget = "s19-rack4-12".parse().unwrap();
+        assert_eq!(name.hostname(), Some("s19-rack4-12"));
+        assert_eq!(name.port(), None);
+
+        let converted = MinerTarget::try_from("miner01.site").unwrap();
+        assert_eq!(converted.hostname(), Some("miner01.site"));
+        assert_eq!(converted.data_ip(), IpAddr::from([0, 0, 0, 0]));
+        assert!(MinerTarget::try_from(String::from("miner 01")).is_err());
+
+        assert!("miner 01".parse::<MinerTarget>().is_err());
+        assert!("miner:http".parse::<MinerTarget>().is_err());
+    }
+}
@@ -1,14 +1,15 @@
 use reqwest::StatusCode;
 use reqwest::header::HeaderMap;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use tokio;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 pub(crate) async fn send_rpc_command(
     ip: &IpAddr,
+    port: u16,
     command: &'static str,
 ) -> Option<serde_json::Value> {
-    let stream = tokio::net::TcpStream::connect(format!("{}:4028", ip)).await;
+    let stream = tokio::net::TcpStream::connect((*ip, port)).await;
     if stream.is_err() {
         return None;
     }
@@ -28,10 +29,17 @@ pub(crate) async fn send_rpc_command(
     parse_rpc_result(&response)
 }
 
+/// Send a GET request to the web server, accepting self-signed certificates over HTTPS
 pub(crate) async fn send_web_command(
     ip: &IpAddr,
+    port: u16,
+    https: bool,
     command: &'static str,
 ) -> Option<(String, HeaderMap, StatusCode)> {
+    let scheme = match https {
+        true => "https",
+        false => "http",
+    };
     let client = reqwest::Client::builder()
         .redirect(reqwest::redirect::Policy::none())
         .danger_accept_invalid_certs(true)
@@ -41,7 +49,12 @@ pub(crate) async fn send_web_command(
     let resp = client
         .execute(
             client
-                .get(format!("http://{}{}", ip, command))
+                .get(format!(
+                    "{}://{}{}",
+                    scheme,
+                    SocketAddr::new(*ip, port),
+                    command
+                ))
                 .build()
                 .expect("Failed to construct request."),
         )
@@ -0,0 +1,505 @@
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::channel::mpsc;
+use futures::{SinkExt, Stream, StreamExt};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::data::miner::MinerData;
+use crate::location::LocationMap;
+use crate::miners::backends::traits::GetMinerData;
+use crate::miners::factory::MinerFactory;
+use crate::miners::target::MinerTarget;
+use crate::registry::{MinerRegistry, RegistryEvent};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_JITTER: Duration = Duration::from_secs(5);
+const DEFAULT_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_REDISCOVERY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_MAX_CONCURRENCY: usize = 64;
+const DEFAULT_OFFLINE_THRESHOLD: u32 = 3;
+const EVENT_BUFFER: usize = 256;
+
+/// An update produced by a running [`MinerMonitor`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorEvent {
+    /// A fresh snapshot of data from a miner.
+    Data(Box<MinerData>),
+    /// A miner answered for the first time, or answered again after being offline.
+    Online { ip: IpAddr },
+    /// A poll of a miner failed, with the number of consecutive failed polls so far.
+    PollFailed { ip: IpAddr, failed_polls: u32 },
+    /// A miner failed enough consecutive polls to be considered offline.
+    Offline { ip: IpAddr, failed_polls: u32 },
+    /// The miner registry detected a change, such as a miner moving to a new IP address.
+    Registry(RegistryEvent),
+}
+
+/// Long-running poller that repeatedly collects `MinerData` from a set of miners.
+///
+/// Each miner is polled on its own interval with random jitter, a shared limit on the
+/// number of concurrent polls, exponential backoff while a miner is not answering, and
+/// a periodic re-run of factory discovery to pick up firmware changes.
+pub struct MinerMonitor {
+    targets: Vec<MinerTarget>,
+    intervals: HashMap<MinerTarget, Duration>,
+    interval: Duration,
+    jitter: Duration,
+    poll_timeout: Duration,
+    max_backoff: Duration,
+    rediscovery_interval: Duration,
+    max_concurrency: usize,
+    offline_threshold: u32,
+    factory: MinerFactory,
+    registry: Arc<Mutex<MinerRegistry>>,
+    locations: Option<Arc<LocationMap>>,
+}
+
+impl MinerMonitor {
+    /// Create a new monitor for the given miner IP addresses or hostnames
+    pub fn new<T: Into<MinerTarget>>(targets: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            targets: targets.into_iter().map(Into::into).collect(),
+            intervals: HashMap::new(),
+            interval: DEFAULT_INTERVAL,
+            jitter: DEFAULT_JITTER,
+            poll_timeout: DEFAULT_POLL_TIMEOUT,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            rediscovery_interval: DEFAULT_REDISCOVERY_INTERVAL,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            offline_threshold: DEFAULT_OFFLINE_THRESHOLD,
+            factory: MinerFactory::new(),
+            registry: Arc::new(Mutex::new(MinerRegistry::new())),
+            locations: None,
+        }
+    }
+
+    /// Set the default polling interval used for every miner
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Override the polling interval for a single miner
+    pub fn with_miner_interval(
+        mut self,
+        target: impl Into<MinerTarget>,
+        interval: Duration,
+    ) -> Self {
+        self.intervals.insert(target.into(), interval);
+        self
+    }
+
+    /// Set the maximum random delay added to each polling interval
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the time allowed for a single discovery or data collection
+    pub fn with_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.poll_timeout = timeout;
+        self
+    }
+
+    /// Set the upper bound on the delay between polls of a miner that is not answering
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set how often factory discovery is re-run for miners that are already identified
+    pub fn with_rediscovery_interval(mut self, interval: Duration) -> Self {
+        self.rediscovery_interval = interval;
+        self
+    }
+
+    /// Set the maximum number of miners being polled at the same time
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Set the number of consecutive failed polls before an `Offline` event is sent
+    pub fn with_offline_threshold(mut self, failed_polls: u32) -> Self {
+        self.offline_threshold = failed_polls.max(1);
+        self
+    }
+
+    /// Set the factory used to identify miners
+    pub fn with_factory(mut self, factory: MinerFactory) -> Self {
+        self.factory = factory;
+        self
+    }
+
+    /// Set the registry every collected snapshot is recorded in, to share it between monitors
+    pub fn with_registry(mut self, registry: Arc<Mutex<MinerRegistry>>) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Set the location mapping used to fill in `MinerData::location`
+    pub fn with_locations(mut self, locations: Arc<LocationMap>) -> Self {
+        self.locations = Some(locations);
+        self
+    }
+
+    /// Start polling in the background and return a stream of events.
+    ///
+    /// Polling stops when the returned stream is dropped.
+    pub fn start(self) -> MonitorStream {
+        let (sender, receiver) = mpsc::channel(EVENT_BUFFER);
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+        let factory = Arc::new(self.factory);
+        let mut tasks = JoinSet::new();
+
+        for target in self.targets {
+            let worker = MinerWorker {
+                ip: target.data_ip(),
+                interval: self
+                    .intervals
+                    .get(&target)
+                    .copied()
+                    .unwrap_or(self.interval),
+                target,
+                jitter: self.jitter,
+                poll_timeout: self.poll_timeout,
+                max_backoff: self.max_backoff,
+                rediscovery_interval: self.rediscovery_interval,
+                offline_threshold: self.offline_threshold,
+                factory: factory.clone(),
+                semaphore: semaphore.clone(),
+                registry: self.registry.clone(),
+                locations: self.locations.clone(),
+                sender: sender.clone(),
+                miner: None,
+            };
+            tasks.spawn(worker.run());
+        }
+
+        MonitorStream {
+            receiver,
+            registry: self.registry,
+            _tasks: tasks,
+        }
+    }
+}
+
+/// Stream of [`MonitorEvent`]s from a running [`MinerMonitor`].
+pub struct MonitorStream {
+    receiver: mpsc::Receiver<MonitorEvent>,
+    registry: Arc<Mutex<MinerRegistry>>,
+    _tasks: JoinSet<()>,
+}
+
+impl MonitorStream {
+    /// The registry linking the polled miners to their stable identities
+    pub fn registry(&self) -> Arc<Mutex<MinerRegistry>> {
+        self.registry.clone()
+    }
+
+    /// Discard online/offline events and only yield miner data
+    pub fn data(self) -> impl Stream<Item = MinerData> {
+        self.filter_map(|event| async move {
+            match event {
+                MonitorEvent::Data(data) => Some(*data),
+                _ => None,
+            }
+        })
+    }
+}
+
+impl Stream for MonitorStream {
+    type Item = MonitorEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+struct MinerWorker {
+    target: MinerTarget,
+    /// The address events are reported for, the last one a hostname target resolved to
+    ip: IpAddr,
+    interval: Duration,
+    jitter: Duration,
+    poll_timeout: Duration,
+    max_backoff: Duration,
+    rediscovery_interval: Duration,
+    offline_threshold: u32,
+    factory: Arc<MinerFactory>,
+    semaphore: Arc<Semaphore>,
+    registry: Arc<Mutex<MinerRegistry>>,
+    locations: Option<Arc<LocationMap>>,
+    sender: mpsc::Sender<MonitorEvent>,
+    /// The backend to poll, found by discovery when there is none yet
+    miner: Option<Box<dyn GetMinerData>>,
+}
+
+impl MinerWorker {
+    async fn run(mut self) {
+        let mut miner = self.miner.take();
+        let mut discovered_at = Instant::now();
+        let mut rediscover = false;
+        let mut failed_polls: u32 = 0;
+        let mut online = false;
+
+        // spread the first round of polls out instead of sending them all at once
+        tokio::time::sleep(random_duration(self.jitter)).await;
+
+        loop {
+            let data = {
+                let Ok(_permit) = self.semaphore.acquire().await else {
+                    return;
+                };
+
+                if miner.is_none()
+                    || rediscover
+                    || discovered_at.elapsed() >= self.rediscovery_interval
+                {
+                    if let Ok(Ok(ip)) =
+                        tokio::time::timeout(self.poll_timeout, self.factory.resolve(&self.target))
+                            .await
+                    {
+                        self.ip = ip;
+                    }
+                    let found = tokio::time::timeout(
+                        self.poll_timeout,
+                        self.factory.get_miner(self.target.clone()),
+                    )
+                    .await
+                    .ok()
+                    .and_then(|result| result.ok().flatten());
+                    // keep the previous backend if the miner did not answer discovery this time
+                    if found.is_some() {
+                        miner = found;
+                        discovered_at = Instant::now();
+                        rediscover = false;
+                    }
+                }
+
+                match &miner {
+                    Some(miner) => tokio::time::timeout(self.poll_timeout, miner.get_data())
+                        .await
+                        .ok()
+                        .filter(has_response),
+                    None => None,
+                }
+            };
+
+            let mut events = Vec::new();
+            match data {
+                Some(mut data) => {
+                    failed_polls = 0;
+                    rediscover = false;
+                    if let Some(locations) = &self.locations {
+                        locations.apply(&mut data);
+                    }
+                    if !online {
+                        online = true;
+                        events.push(MonitorEvent::Online { ip: self.ip });
+                    }
+                    let changes = self
+                        .registry
+                        .lock()
+                        .expect("Miner registry lock poisoned")
+                        .observe(&data);
+                    events.extend(changes.into_iter().map(MonitorEvent::Registry));
+                    events.push(MonitorEvent::Data(Box::new(data)));
+                }
+                None => {
+                    failed_polls = failed_polls.saturating_add(1);
+                    events.push(MonitorEvent::PollFailed {
+                        ip: self.ip,
+                        failed_polls,
+                    });
+                    if online && failed_polls >= self.offline_threshold {
+                        online = false;
+                        // discover again in case the firmware changed while it was away,
+                        // keeping the old backend if discovery does not find anything
+                        rediscover = true;
+                        events.push(MonitorEvent::Offline {
+                            ip: self.ip,
+                            failed_polls,
+                        });
+                    }
+                }
+            }
+
+            for event in events {
+                if self.sender.send(event).await.is_err() {
+                    // the stream was dropped, nobody is listening anymore
+                    return;
+                }
+            }
+
+            let delay = backoff_delay(self.interval, failed_polls, self.max_backoff)
+                + random_duration(self.jitter);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Whether the miner actually answered, as backends fill in defaults when every command fails
+fn has_response(data: &MinerData) -> bool {
+    data.mac.is_some()
+        || data.hashrate.is_some()
+        || data.uptime.is_some()
+        || data.firmware_version.is_some()
+}
+
+/// Doubles the polling interval for each consecutive failure, up to `max_backoff`
+fn backoff_delay(interval: Duration, failed_polls: u32, max_backoff: Duration) -> Duration {
+    let factor = 1u32 << failed_polls.min(16);
+    interval
+        .saturating_mul(factor)
+        .min(max_backoff.max(interval))
+}
+
+fn random_duration(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+    let hasher = RandomState::new().build_hasher();
+    let nanos = hasher.finish() % max.as_nanos().min(u64::MAX as u128) as u64;
+    Duration::from_nanos(nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miners::backends::traits::RawCommandError;
+    use crate::miners::commands::MinerCommand;
+    use crate::miners::data::{DataField, DataLocation};
+    use async_trait::async_trait;
+    use serde_json::Value;
+    use std::collections::VecDeque;
+
+    /// Answers or fails polls in a scripted order, then keeps answering
+    struct ScriptedMiner {
+        ip: IpAddr,
+        answers: Mutex<VecDeque<bool>>,
+    }
+
+    #[async_trait]
+    impl GetMinerData for ScriptedMiner {
+        async fn get_data_fields(&self, _fields: &[DataField]) -> MinerData {
+            let mut data = MinerData::empty(self.ip);
+            if self.answers.lock().unwrap().pop_front().unwrap_or(true) {
+                data.uptime = Some(Duration::from_secs(60));
+            }
+            data
+        }
+
+        fn get_locations(&self, _data_field: DataField) -> &'static [DataLocation] {
+            &[]
+        }
+
+        async fn send_raw(
+            &self,
+            command: MinerCommand,
+            _params: Option<Value>,
+        ) -> Result<Value, RawCommandError> {
+            Err(RawCommandError::UnsupportedTransport(command))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_and_online_once() {
+        let ip = IpAddr::from([10, 0, 0, 1]);
+        // up, then down for one poll more than the threshold, then back up
+        let answers = [true, false, false, false, false, true, true];
+        let miner = ScriptedMiner {
+            ip,
+            answers: Mutex::new(answers.into_iter().collect()),
+        };
+        // a factory that searches for nothing, so rediscovery finds nothing and the
+        // scripted backend is kept
+        let mut factory = MinerFactory::new();
+        factory.with_search_makes(vec![]);
+        factory.with_search_firmwares(vec![]);
+
+        let (sender, receiver) = mpsc::channel(EVENT_BUFFER);
+        let worker = MinerWorker {
+            target: ip.into(),
+            ip,
+            interval: Duration::from_millis(1),
+            jitter: Duration::ZERO,
+            poll_timeout: Duration::from_secs(1),
+            max_backoff: Duration::from_millis(1),
+            rediscovery_interval: DEFAULT_REDISCOVERY_INTERVAL,
+            offline_threshold: 3,
+            factory: Arc::new(factory),
+            semaphore: Arc::new(Semaphore::new(1)),
+            registry: Arc::new(Mutex::new(MinerRegistry::new())),
+            locations: None,
+            sender,
+            miner: Some(Box::new(miner)),
+        };
+        let task = tokio::spawn(worker.run());
+
+        let events: Vec<MonitorEvent> = receiver
+            .filter(|event| {
+                let keep = matches!(
+                    event,
+                    MonitorEvent::Online { .. }
+                        | MonitorEvent::Offline { .. }
+                        | MonitorEvent::PollFailed { .. }
+                );
+                async move { keep }
+            })
+            .take(7)
+            .collect()
+            .await;
+        task.abort();
+
+        assert_eq!(
+            events,
+            vec![
+                MonitorEvent::Online { ip },
+                MonitorEvent::PollFailed {
+                    ip,
+                    failed_polls: 1
+                },
+                MonitorEvent::PollFailed {
+                    ip,
+                    failed_polls: 2
+                },
+                MonitorEvent::PollFailed {
+                    ip,
+                    failed_polls: 3
+                },
+                MonitorEvent::Offline {
+                    ip,
+                    failed_polls: 3
+                },
+                MonitorEvent::PollFailed {
+                    ip,
+                    failed_polls: 4
+                },
+                MonitorEvent::Online { ip },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let interval = Duration::from_secs(10);
+        let max_backoff = Duration::from_secs(60);
+
+        assert_eq!(backoff_delay(interval, 0, max_backoff), interval);
+        assert_eq!(
+            backoff_delay(interval, 2, max_backoff),
+            Duration::from_secs(40)
+        );
+        assert_eq!(backoff_delay(interval, 10, max_backoff), max_backoff);
+    }
+}
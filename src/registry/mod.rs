@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::net::IpAddr;
+
+use macaddr::MacAddr;
+
+use crate::data::device::MinerModel;
+use crate::data::miner::MinerData;
+
+/// A stable identity for a miner that survives IP address changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MinerIdentity {
+    /// Identified by the MAC address of the control board
+    Mac(MacAddr),
+    /// Identified by the control board serial number
+    Serial(String),
+    /// Identified by the sorted serial numbers of the installed hashboards
+    Hashboards(Vec<String>),
+}
+
+impl MinerIdentity {
+    /// Get the strongest identity available in a set of miner data.
+    ///
+    /// The MAC address is preferred, falling back to the control board serial,
+    /// then the hashboard serials. Returns `None` if the data contains none of these.
+    pub fn from_data(data: &MinerData) -> Option<Self> {
+        if let Some(mac) = data.mac {
+            return Some(Self::Mac(mac));
+        }
+        if let Some(serial) = data.serial_number.as_ref().filter(|s| !s.is_empty()) {
+            return Some(Self::Serial(serial.clone()));
+        }
+        let boards = board_serials(data);
+        if !boards.is_empty() {
+            return Some(Self::Hashboards(boards));
+        }
+        None
+    }
+}
+
+impl Display for MinerIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MinerIdentity::Mac(mac) => write!(f, "mac:{}", mac),
+            MinerIdentity::Serial(serial) => write!(f, "serial:{}", serial),
+            MinerIdentity::Hashboards(serials) => write!(f, "boards:{}", serials.join(",")),
+        }
+    }
+}
+
+/// Everything the registry knows about a single miner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinerRecord {
+    /// The stable identity of this miner
+    pub identity: MinerIdentity,
+    /// The IP address the miner was last seen on
+    pub ip: IpAddr,
+    /// Every IP address this miner has been seen on, with the timestamp it was first seen there
+    pub ip_history: Vec<(u64, IpAddr)>,
+    /// The MAC address of the miner, if it has been reported
+    pub mac: Option<MacAddr>,
+    /// The control board serial number, if it has been reported
+    pub serial_number: Option<String>,
+    /// The serial numbers of the hashboards currently installed
+    pub board_serials: Vec<String>,
+    /// The model of the miner
+    pub model: MinerModel,
+    /// The timestamp this miner was first seen
+    pub first_seen: u64,
+    /// The timestamp this miner was last seen
+    pub last_seen: u64,
+}
+
+/// A change detected by the [`MinerRegistry`] while observing miner data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryEvent {
+    /// A miner was seen for the first time
+    NewMiner { identity: MinerIdentity, ip: IpAddr },
+    /// A known miner was seen on a different IP address
+    IpChanged {
+        identity: MinerIdentity,
+        old_ip: IpAddr,
+        new_ip: IpAddr,
+    },
+    /// A hashboard that was last seen in one miner is now reported by another
+    HashboardMoved {
+        serial_number: String,
+        from: MinerIdentity,
+        to: MinerIdentity,
+    },
+}
+
+/// Registry of miners keyed on a stable [`MinerIdentity`].
+///
+/// Feed it every `MinerData` snapshot with [`MinerRegistry::observe`] to link readings across
+/// DHCP address changes and to detect hashboards being swapped between machines.
+#[derive(Debug, Default)]
+pub struct MinerRegistry {
+    records: HashMap<MinerIdentity, MinerRecord>,
+    board_owners: HashMap<String, MinerIdentity>,
+}
+
+impl MinerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a snapshot of miner data and return any changes it reveals
+    pub fn observe(&mut self, data: &MinerData) -> Vec<RegistryEvent> {
+        let mut events = Vec::new();
+        let Some(identity) = self.resolve(data) else {
+            return events;
+        };
+        let boards = board_serials(data);
+
+        match self.records.get_mut(&identity) {
+            Some(record) => {
+                if record.ip != data.ip {
+                    events.push(RegistryEvent::IpChanged {
+                        identity: identity.clone(),
+                        old_ip: record.ip,
+                        new_ip: data.ip,
+                    });
+                    record.ip = data.ip;
+                    record.ip_history.push((data.timestamp, data.ip));
+                }
+                record.mac = data.mac.or(record.mac);
+                record.serial_number = data.serial_number.clone().or(record.serial_number.take());
+                if !boards.is_empty() {
+                    record.board_serials = boards.clone();
+                }
+                record.model = data.device_info.model.clone();
+                record.last_seen = data.timestamp;
+            }
+            None => {
+                events.push(RegistryEvent::NewMiner {
+                    identity: identity.clone(),
+                    ip: data.ip,
+                });
+                self.records.insert(
+                    identity.clone(),
+                    MinerRecord {
+                        identity: identity.clone(),
+                        ip: data.ip,
+                        ip_history: vec![(data.timestamp, data.ip)],
+                        mac: data.mac,
+                        serial_number: data.serial_number.clone(),
+                        board_serials: boards.clone(),
+                        model: data.device_info.model.clone(),
+                        first_seen: data.timestamp,
+                        last_seen: data.timestamp,
+                    },
+                );
+            }
+        }
+
+        for serial_number in boards {
+            let previous = self
+                .board_owners
+                .insert(serial_number.clone(), identity.clone());
+            if let Some(from) = previous.filter(|owner| *owner != identity) {
+                if let Some(previous_record) = self.records.get_mut(&from) {
+                    previous_record
+                        .board_serials
+                        .retain(|s| *s != serial_number);
+                }
+                events.push(RegistryEvent::HashboardMoved {
+                    serial_number,
+                    from,
+                    to: identity.clone(),
+                });
+            }
+        }
+
+        events
+    }
+
+    /// Find the identity of the miner this data belongs to.
+    ///
+    /// Known miners are matched by MAC, control board serial, then hashboard serials, so a
+    /// miner keeps its identity even if a reading is missing its MAC address.
+    pub fn resolve(&self, data: &MinerData) -> Option<MinerIdentity> {
+        if let Some(mac) = data.mac {
+            let identity = MinerIdentity::Mac(mac);
+            if self.records.contains_key(&identity) {
+                return Some(identity);
+            }
+        }
+        if let Some(serial) = data.serial_number.as_ref().filter(|s| !s.is_empty()) {
+            if let Some(record) = self
+                .records
+                .values()
+                .find(|r| r.serial_number.as_ref() == Some(serial))
+            {
+                return Some(record.identity.clone());
+            }
+        }
+        let boards = board_serials(data);
+        if !boards.is_empty() {
+            if let Some(record) = self
+                .records
+                .values()
+                .find(|r| !r.board_serials.is_empty() && r.board_serials == boards)
+            {
+                return Some(record.identity.clone());
+            }
+        }
+        MinerIdentity::from_data(data)
+    }
+
+    /// Get the record for a miner identity
+    pub fn get(&self, identity: &MinerIdentity) -> Option<&MinerRecord> {
+        self.records.get(identity)
+    }
+
+    /// Get the record of the miner last seen on an IP address
+    pub fn find_by_ip(&self, ip: IpAddr) -> Option<&MinerRecord> {
+        self.records.values().find(|r| r.ip == ip)
+    }
+
+    /// Get the record of the miner a hashboard is currently installed in
+    pub fn find_by_board_serial(&self, serial_number: &str) -> Option<&MinerRecord> {
+        self.records.get(self.board_owners.get(serial_number)?)
+    }
+
+    /// Iterate over every known miner
+    pub fn records(&self) -> impl Iterator<Item = &MinerRecord> {
+        self.records.values()
+    }
+}
+
+fn board_serials(data: &MinerData) -> Vec<String> {
+    let mut serials: Vec<String> = data
+        .hashboards
+        .iter()
+        .filter_map(|b| b.serial_number.clone())
+        .filter(|s| !s.is_empty())
+        .collect();
+    serials.sort();
+    serials
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::board::BoardData;
+    use std::str::FromStr;
+
+    fn data(ip: [u8; 4], mac: Option<&str>, serial: Option<&str>, boards: &[&str]) -> MinerData {
+        let mut data = MinerData::empty(IpAddr::from(ip));
+        data.mac = mac.map(|mac| MacAddr::from_str(mac).unwrap());
+        data.serial_number = serial.map(String::from);
+        data.hashboards = boards
+            .iter()
+            .enumerate()
+            .map(|(position, serial)| BoardData {
+                position: position as u8,
+                hashrate: None,
+                expected_hashrate: None,
+                board_temperature: None,
+                intake_temperature: None,
+                outlet_temperature: None,
+                expected_chips: None,
+                working_chips: None,
+                serial_number: Some(serial.to_string()),
+                chips: vec![],
+                voltage: None,
+                frequency: None,
+                tuned: None,
+                active: None,
+            })
+            .collect();
+        data
+    }
+
+    #[test]
+    fn test_ip_change_keeps_mac_identity() {
+        let mut registry = MinerRegistry::new();
+        let identity = MinerIdentity::Mac(MacAddr::from_str("c4:08:28:00:11:22").unwrap());
+
+        let events = registry.observe(&data([10, 0, 0, 5], Some("c4:08:28:00:11:22"), None, &[]));
+        assert_eq!(
+            events,
+            vec![RegistryEvent::NewMiner {
+                identity: identity.clone(),
+                ip: IpAddr::from([10, 0, 0, 5]),
+            }]
+        );
+
+        let events = registry.observe(&data([10, 0, 0, 9], Some("c4:08:28:00:11:22"), None, &[]));
+        assert_eq!(
+            events,
+            vec![RegistryEvent::IpChanged {
+                identity: identity.clone(),
+                old_ip: IpAddr::from([10, 0, 0, 5]),
+                new_ip: IpAddr::from([10, 0, 0, 9]),
+            }]
+        );
+        let record = registry.get(&identity).unwrap();
+        assert_eq!(record.ip_history.len(), 2);
+        assert_eq!(registry.records().count(), 1);
+    }
+
+    #[test]
+    fn test_serial_only_match() {
+        let mut registry = MinerRegistry::new();
+        let identity = MinerIdentity::Serial(String::from("JYZZ1234"));
+
+        let events = registry.observe(&data([10, 0, 0, 5], None, Some("JYZZ1234"), &[]));
+        assert_eq!(
+            events,
+            vec![RegistryEvent::NewMiner {
+                identity: identity.clone(),
+                ip: IpAddr::from([10, 0, 0, 5]),
+            }]
+        );
+
+        // a later reading with a MAC still belongs to the miner first seen by serial
+        let events = registry.observe(&data(
+            [10, 0, 0, 6],
+            Some("c4:08:28:00:11:22"),
+            Some("JYZZ1234"),
+            &[],
+        ));
+        assert_eq!(
+            events,
+            vec![RegistryEvent::IpChanged {
+                identity: identity.clone(),
+                old_ip: IpAddr::from([10, 0, 0, 5]),
+                new_ip: IpAddr::from([10, 0, 0, 6]),
+            }]
+        );
+        assert!(registry.get(&identity).unwrap().mac.is_some());
+    }
+
+    #[test]
+    fn test_hashboard_moved() {
+        let mut registry = MinerRegistry::new();
+        let first = MinerIdentity::Mac(MacAddr::from_str("c4:08:28:00:00:01").unwrap());
+        let second = MinerIdentity::Mac(MacAddr::from_str("c4:08:28:00:00:02").unwrap());
+
+        registry.observe(&data(
+            [10, 0, 0, 1],
+            Some("c4:08:28:00:00:01"),
+            None,
+            &["B1", "B2"],
+        ));
+        registry.observe(&data(
+            [10, 0, 0, 2],
+            Some("c4:08:28:00:00:02"),
+            None,
+            &["B3"],
+        ));
+
+        let events = registry.observe(&data(
+            [10, 0, 0, 2],
+            Some("c4:08:28:00:00:02"),
+            None,
+            &["B2", "B3"],
+        ));
+        assert_eq!(
+            events,
+            vec![RegistryEvent::HashboardMoved {
+                serial_number: String::from("B2"),
+                from: first.clone(),
+                to: second.clone(),
+            }]
+        );
+        assert_eq!(registry.get(&first).unwrap().board_serials, vec!["B1"]);
+        assert_eq!(
+            registry.find_by_board_serial("B2").unwrap().identity,
+            second
+        );
+    }
+}